@@ -0,0 +1,21 @@
+// Wasm96 V SDK allocator exports
+module alloc
+
+// Importing this module exports wasm96_alloc and wasm96_free, which the host uses to
+// place data returned by calls such as storage_load and asset_read into guest memory.
+
+// Allocate size bytes for the host.
+@[export: 'wasm96_alloc']
+pub fn wasm96_alloc(size u32) &u8 {
+	return unsafe { malloc(int(size)) }
+}
+
+// Free memory previously returned by wasm96_alloc.
+@[export: 'wasm96_free']
+pub fn wasm96_free(ptr &u8, size u32) {
+	unsafe { free(ptr) }
+}
+
+// Call once during setup. The exports are installed by importing this module;
+// calling enable keeps V from rejecting the import as unused.
+pub fn enable() {}
@@ -1,6 +1,8 @@
 // Wasm96 V SDK
 module wasm96
 
+import math
+
 // Joypad button ids.
 pub enum Button as u32 {
 	b = 0
@@ -21,24 +23,186 @@ pub enum Button as u32 {
 	r3 = 15
 }
 
+// Horizontal text alignment relative to the anchor point.
+pub enum HAlign as u32 {
+	left = 0
+	center = 1
+	right = 2
+}
+
+// Vertical text alignment relative to the anchor point.
+pub enum VAlign as u32 {
+	top = 0
+	middle = 1
+	bottom = 2
+}
+
+// Analog stick ids.
+pub enum Stick as u32 {
+	left = 0
+	right = 1
+}
+
+// Local date and time reported by the host.
+pub struct DateTime {
+pub:
+	year    u16
+	month   u8 // 1 to 12
+	day     u8 // 1 to 31
+	hour    u8
+	minute  u8
+	second  u8
+	weekday u8 // 0 = Sunday
+}
+
+// A message received from a peer.
+pub struct PeerMessage {
+pub:
+	peer u32
+	data []u8
+}
+
+// Per-frame drawing statistics filled in by the host.
+// The field order matches the layout written by wasm96_graphics_stats.
+pub struct GraphicsStats {
+pub:
+	points         u32
+	lines          u32
+	rects          u32
+	circles        u32
+	triangles      u32
+	curves         u32
+	images         u32
+	texts          u32
+	meshes         u32
+	pixels_blitted u32
+}
+
+// Checkerboard dither pattern for graphics_set_pattern.
+pub const pattern_checker = [u8(0xAA), 0x55, 0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55]!
+
 // Text size dimensions.
 pub struct TextSize {
 	width u32
 	height u32
 }
 
+// Audio data formats accepted by audio channels.
+pub enum AudioFormat as u32 {
+	wav = 0
+	qoa = 1
+	xm = 2
+}
+
+// Kinds of asset accepted by register_assets.
+pub enum AssetKind {
+	png
+	gif
+	svg
+	ttf
+	bdf
+	spleen
+}
+
+// Screen transition styles.
+pub enum TransitionKind {
+	fade
+	wipe
+	iris
+}
+
+// Kinds of host information available from system_info.
+pub enum InfoKind as u32 {
+	core_version = 0
+	abi_version = 1
+	backend = 2
+	frontend = 3
+}
+
+// Log message severity.
+pub enum LogLevel as u32 {
+	debug = 0
+	info = 1
+	warn = 2
+	error = 3
+}
+
+// Fields of the wasm96.meta custom section.
+pub enum MetaField as u32 {
+	title = 0
+	author = 1
+	version = 2
+	icon = 3
+}
+
+// Storage status codes returned by the host from save, settings save and commit.
+pub enum StorageStatus as u32 {
+	ok = 0
+	quota_exceeded = 1
+	invalid_key = 2
+	io_error = 3
+}
+
+// Error returned when a storage operation fails.
+pub struct StorageError {
+	Error
+pub:
+	status StorageStatus
+}
+
+// Human-readable description of the failure.
+pub fn (e StorageError) msg() string {
+	return match e.status {
+		.ok { 'storage: ok' }
+		.quota_exceeded { 'storage: quota exceeded' }
+		.invalid_key { 'storage: invalid key' }
+		.io_error { 'storage: io error' }
+	}
+}
+
+// The raw StorageStatus value.
+pub fn (e StorageError) code() int {
+	return int(e.status)
+}
+
+// Error reported by the host for a failed call.
+pub struct Wasm96Error {
+	Error
+pub:
+	message string
+}
+
+// The message reported by the host.
+pub fn (e Wasm96Error) msg() string {
+	return e.message
+}
+
 // Low-level raw ABI imports.
 
 // Graphics
 fn C.wasm96_graphics_set_size(width u32, height u32)
 fn C.wasm96_graphics_set_color(r u32, g u32, b u32, a u32)
 fn C.wasm96_graphics_background(r u32, g u32, b u32)
+fn C.wasm96_graphics_palette_set(colors_ptr &Color, count u32)
+fn C.wasm96_graphics_palette_enable(enable u32)
+fn C.wasm96_graphics_set_color_index(index u32)
+fn C.wasm96_graphics_image_indexed(x int, y int, w u32, h u32, ptr &u8, len usize)
+fn C.wasm96_graphics_set_stroke_width(px u32)
+fn C.wasm96_graphics_set_pattern(ptr &u8)
+fn C.wasm96_graphics_pattern_reset()
+fn C.wasm96_graphics_clip(x int, y int, w u32, h u32)
+fn C.wasm96_graphics_clip_reset()
+fn C.wasm96_graphics_layer_begin(key_ptr &u8, key_len usize, z int)
+fn C.wasm96_graphics_layer_end()
 fn C.wasm96_graphics_point(x int, y int)
 fn C.wasm96_graphics_line(x1 int, y1 int, x2 int, y2 int)
 fn C.wasm96_graphics_rect(x int, y int, w u32, h u32)
 fn C.wasm96_graphics_rect_outline(x int, y int, w u32, h u32)
 fn C.wasm96_graphics_circle(x int, y int, r u32)
 fn C.wasm96_graphics_circle_outline(x int, y int, r u32)
+fn C.wasm96_graphics_polyline(points_ptr &int, count u32, closed u32)
+fn C.wasm96_graphics_ellipse(x int, y int, rx u32, ry u32)
+fn C.wasm96_graphics_ellipse_outline(x int, y int, rx u32, ry u32)
 fn C.wasm96_graphics_image(x int, y int, w u32, h u32, ptr &u8, len usize)
 fn C.wasm96_graphics_image_png(x int, y int, ptr &u8, len usize)
 fn C.wasm96_graphics_triangle(x1 int, y1 int, x2 int, y2 int, x3 int, y3 int)
@@ -47,23 +211,43 @@ fn C.wasm96_graphics_bezier_quadratic(x1 int, y1 int, cx int, cy int, x2 int, y2
 fn C.wasm96_graphics_bezier_cubic(x1 int, y1 int, cx1 int, cy1 int, cx2 int, cy2 int, x2 int, y2 int, segments u32)
 fn C.wasm96_graphics_pill(x int, y int, w u32, h u32)
 fn C.wasm96_graphics_pill_outline(x int, y int, w u32, h u32)
-fn C.wasm96_graphics_svg_register(key u64, data_ptr &u8, data_len usize) u32
-fn C.wasm96_graphics_svg_draw_key(key u64, x int, y int, w u32, h u32)
-fn C.wasm96_graphics_svg_unregister(key u64)
-fn C.wasm96_graphics_gif_register(key u64, data_ptr &u8, data_len usize) u32
-fn C.wasm96_graphics_gif_draw_key(key u64, x int, y int)
-fn C.wasm96_graphics_gif_draw_key_scaled(key u64, x int, y int, w u32, h u32)
-fn C.wasm96_graphics_gif_unregister(key u64)
-fn C.wasm96_graphics_png_register(key u64, data_ptr &u8, data_len usize) u32
-fn C.wasm96_graphics_png_draw_key(key u64, x int, y int)
-fn C.wasm96_graphics_png_draw_key_scaled(key u64, x int, y int, w u32, h u32)
-fn C.wasm96_graphics_png_unregister(key u64)
-fn C.wasm96_graphics_font_register_ttf(key u64, data_ptr &u8, data_len usize) u32
-fn C.wasm96_graphics_font_register_bdf(key u64, data_ptr &u8, data_len usize) u32
-fn C.wasm96_graphics_font_register_spleen(key u64, size u32) u32
-fn C.wasm96_graphics_font_unregister(key u64)
-fn C.wasm96_graphics_text_key(x int, y int, font_key u64, text_ptr &u8, text_len usize)
-fn C.wasm96_graphics_text_measure_key(font_key u64, text_ptr &u8, text_len usize) u64
+fn C.wasm96_graphics_svg_register(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_graphics_svg_draw_key(key_ptr &u8, key_len usize, x int, y int, w u32, h u32)
+fn C.wasm96_graphics_svg_draw_key_ex(key_ptr &u8, key_len usize, x int, y int, w u32, h u32, angle f32, opacity f32)
+fn C.wasm96_graphics_svg_unregister(key_ptr &u8, key_len usize)
+fn C.wasm96_graphics_gif_register(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_graphics_gif_draw_key(key_ptr &u8, key_len usize, x int, y int)
+fn C.wasm96_graphics_gif_draw_key_scaled(key_ptr &u8, key_len usize, x int, y int, w u32, h u32)
+fn C.wasm96_graphics_gif_draw_key_at(key_ptr &u8, key_len usize, x int, y int, w u32, h u32, time_ms u64)
+fn C.wasm96_graphics_gif_unregister(key_ptr &u8, key_len usize)
+fn C.wasm96_graphics_png_register(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_graphics_png_draw_key(key_ptr &u8, key_len usize, x int, y int)
+fn C.wasm96_graphics_png_draw_key_scaled(key_ptr &u8, key_len usize, x int, y int, w u32, h u32)
+fn C.wasm96_graphics_png_draw_region(key_ptr &u8, key_len usize, sx int, sy int, sw u32, sh u32, dx int, dy int)
+fn C.wasm96_graphics_png_draw_region_scaled(key_ptr &u8, key_len usize, sx int, sy int, sw u32, sh u32, dx int, dy int, dw u32, dh u32)
+fn C.wasm96_graphics_png_draw_nine_slice(key_ptr &u8, key_len usize, x int, y int, w u32, h u32, left u32, top u32, right u32, bottom u32)
+fn C.wasm96_graphics_png_unregister(key_ptr &u8, key_len usize)
+fn C.wasm96_graphics_sprite_register(key_ptr &u8, key_len usize, w u32, h u32, rgba_ptr &u8, rgba_len usize) u32
+fn C.wasm96_graphics_sprite_draw_key(key_ptr &u8, key_len usize, x int, y int)
+fn C.wasm96_graphics_sprite_draw_key_scaled(key_ptr &u8, key_len usize, x int, y int, w u32, h u32)
+fn C.wasm96_graphics_sprite_unregister(key_ptr &u8, key_len usize)
+fn C.wasm96_graphics_tilemap_register(key_ptr &u8, key_len usize, tileset_ptr &u8, tileset_len usize, tile_w u32, tile_h u32) u32
+fn C.wasm96_graphics_tilemap_set_data(key_ptr &u8, key_len usize, width u32, height u32, tiles_ptr &int, tiles_len usize)
+fn C.wasm96_graphics_tilemap_draw(key_ptr &u8, key_len usize, cam_x int, cam_y int)
+fn C.wasm96_graphics_tilemap_unregister(key_ptr &u8, key_len usize)
+fn C.wasm96_graphics_font_register_ttf(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_graphics_font_register_bdf(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_graphics_font_register_spleen(key_ptr &u8, key_len usize, size u32) u32
+fn C.wasm96_graphics_font_set_size(key_ptr &u8, key_len usize, px f32)
+fn C.wasm96_graphics_font_unregister(key_ptr &u8, key_len usize)
+fn C.wasm96_graphics_text_key(x int, y int, font_key_ptr &u8, font_key_len usize, text_ptr &u8, text_len usize)
+fn C.wasm96_graphics_text_key_aligned(x int, y int, font_key_ptr &u8, font_key_len usize, text_ptr &u8, text_len usize, h_align u32, v_align u32)
+fn C.wasm96_graphics_text_key_wrapped(x int, y int, max_width u32, line_height u32, font_key_ptr &u8, font_key_len usize, text_ptr &u8, text_len usize) u32
+fn C.wasm96_graphics_text_measure_key(font_key_ptr &u8, font_key_len usize, text_ptr &u8, text_len usize) u64
+
+fn C.wasm96_graphics_get_pixel(x int, y int) u32
+fn C.wasm96_graphics_read_rect(x int, y int, w u32, h u32, out_ptr &u8, out_len usize)
+fn C.wasm96_graphics_stats(out_ptr &GraphicsStats)
 
 fn C.wasm96_graphics_set_3d(enable u32)
 fn C.wasm96_graphics_camera_look_at(eye_x f32, eye_y f32, eye_z f32, target_x f32, target_y f32, target_z f32, up_x f32, up_y f32, up_z f32)
@@ -79,6 +263,9 @@ fn C.wasm96_input_is_key_down(key u32) u32
 fn C.wasm96_input_get_mouse_x() int
 fn C.wasm96_input_get_mouse_y() int
 fn C.wasm96_input_is_mouse_down(btn u32) u32
+fn C.wasm96_input_next_char() u32
+fn C.wasm96_input_get_analog(port u32, stick u32, axis u32) i32
+fn C.wasm96_input_set_rumble(port u32, strong u32, weak u32)
 
 // Audio
 fn C.wasm96_audio_init(sample_rate u32) u32
@@ -86,13 +273,185 @@ fn C.wasm96_audio_push_samples(ptr &i16, len usize)
 fn C.wasm96_audio_play_wav(ptr &u8, len usize)
 fn C.wasm96_audio_play_qoa(ptr &u8, len usize)
 fn C.wasm96_audio_play_xm(ptr &u8, len usize)
+fn C.wasm96_audio_channel_play(format u32, ptr &u8, len usize, looping u32) u32
+fn C.wasm96_audio_channel_stop(channel u32)
+fn C.wasm96_audio_channel_set_volume(channel u32, volume f32)
+
+// Storage
+fn C.wasm96_storage_save(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_storage_load(key_ptr &u8, key_len usize) u64
+fn C.wasm96_storage_size(key_ptr &u8, key_len usize) u64
+fn C.wasm96_storage_load_chunk(key_ptr &u8, key_len usize, offset u64, dst_ptr &u8, dst_len usize) u32
+fn C.wasm96_storage_delete(key_ptr &u8, key_len usize)
+fn C.wasm96_storage_exists(key_ptr &u8, key_len usize) u32
+fn C.wasm96_storage_list(prefix_ptr &u8, prefix_len usize, out_ptr &u8, out_cap usize) u32
+fn C.wasm96_storage_begin()
+fn C.wasm96_storage_commit() u32
+fn C.wasm96_settings_save(key_ptr &u8, key_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_settings_load(key_ptr &u8, key_len usize) u64
+
+// Assets
+fn C.wasm96_asset_read(path_ptr &u8, path_len usize) u64
+fn C.wasm96_asset_register_png(key_ptr &u8, key_len usize, path_ptr &u8, path_len usize) u32
+fn C.wasm96_cart_meta(field u32, out_ptr &u8, out_cap usize) u32
+
+// Network
+fn C.wasm96_net_udp_open(port u32) u32
+fn C.wasm96_net_udp_send(socket u32, addr_ptr &u8, addr_len usize, data_ptr &u8, data_len usize) u32
+fn C.wasm96_net_udp_recv(socket u32, out_ptr &u8, out_cap usize) u32
+fn C.wasm96_net_udp_close(socket u32)
+fn C.wasm96_net_tcp_connect(addr_ptr &u8, addr_len usize) u32
+fn C.wasm96_net_tcp_send(conn u32, data_ptr &u8, data_len usize) u32
+fn C.wasm96_net_tcp_recv(conn u32, out_ptr &u8, out_cap usize) u32
+fn C.wasm96_net_tcp_close(conn u32)
+fn C.wasm96_net_http_get(url_ptr &u8, url_len usize) u32
+fn C.wasm96_net_http_status(request u32) i32
+fn C.wasm96_net_http_body_len(request u32) u32
+fn C.wasm96_net_http_read_body(request u32, out_ptr &u8, out_cap usize) u32
+fn C.wasm96_net_http_free(request u32)
+fn C.wasm96_net_peer_host(out_ptr &u8, out_cap usize) u32
+fn C.wasm96_net_peer_join(code_ptr &u8, code_len usize) u32
+fn C.wasm96_net_peer_leave()
+fn C.wasm96_net_peer_count() u32
+fn C.wasm96_net_peer_send(peer u32, reliable u32, data_ptr &u8, data_len usize) u32
+fn C.wasm96_net_peer_recv(out_ptr &u8, out_cap usize) u64
+
+// Options
+fn C.wasm96_option_define(key_ptr &u8, key_len usize, label_ptr &u8, label_len usize, values_ptr &u8, values_len usize)
+fn C.wasm96_option_get(key_ptr &u8, key_len usize, out_ptr &u8, out_cap usize) u32
 
 // System
 fn C.wasm96_system_log(ptr &u8, len usize)
+fn C.wasm96_system_log_level(level u32, ptr &u8, len usize)
 fn C.wasm96_system_millis() u64
+fn C.wasm96_system_last_error(out_ptr &u8, out_cap usize) u32
+fn C.wasm96_system_random_u64() u64
+fn C.wasm96_system_datetime() u64
+fn C.wasm96_system_delta_micros() u64
+fn C.wasm96_system_frame_count() u64
+fn C.wasm96_system_get_locale(out_ptr &u8, out_cap usize) u32
+fn C.wasm96_system_exit()
+fn C.wasm96_system_screenshot(key_ptr &u8, key_len usize) u32
+fn C.wasm96_system_info(kind u32, out_ptr &u8, out_cap usize) u32
+fn C.wasm96_system_content_dir(out_ptr &u8, out_cap usize) u32
+fn C.wasm96_system_display_hz() f32
+fn C.wasm96_debug_screenshot()
+fn C.wasm96_perf_begin(name_ptr &u8, name_len usize)
+fn C.wasm96_perf_end(name_ptr &u8, name_len usize)
+
+// App API.
+
+// A game driven by the setup, update and draw exports.
+pub interface App {
+mut:
+	setup()
+	update()
+	draw()
+}
+
+struct NoApp {}
+
+fn (mut a NoApp) setup() {}
+
+fn (mut a NoApp) update() {}
+
+fn (mut a NoApp) draw() {}
+
+__global wasm96_app = App(NoApp{})
+
+// Set the game instance that receives setup, update and draw calls.
+pub fn set_app(app App) {
+	wasm96_app = app
+}
+
+// Forward a setup call to the registered app.
+pub fn app_setup() {
+	wasm96_app.setup()
+}
+
+// Forward an update call to the registered app.
+pub fn app_update() {
+	wasm96_app.update()
+}
+
+// Forward a draw call to the registered app.
+pub fn app_draw() {
+	wasm96_app.draw()
+}
+
+// When built with -d wasm96_app the SDK provides the setup, update and draw exports.
+
+@[export: 'setup']
+@[if wasm96_app ?]
+fn export_setup() {
+	if wasm96_app is NoApp {
+		log_error('wasm96: setup called before set_app; is init running?')
+		return
+	}
+	app_setup()
+}
+
+@[export: 'update']
+@[if wasm96_app ?]
+fn export_update() {
+	app_update()
+}
+
+@[export: 'draw']
+@[if wasm96_app ?]
+fn export_draw() {
+	app_draw()
+}
+
+// Key API.
+
+// A key namespace, so libraries used by one guest don't clash over resource or storage keys.
+pub struct KeyNs {
+pub:
+	prefix string
+}
+
+// Create a key namespace.
+pub fn key_ns(name string) KeyNs {
+	return KeyNs{
+		prefix: key_escape(name)
+	}
+}
+
+// Build a key inside this namespace.
+pub fn (ns KeyNs) key(name string) []u8 {
+	return '${ns.prefix}/${key_escape(name)}'.bytes()
+}
+
+// Create a nested namespace.
+pub fn (ns KeyNs) sub(name string) KeyNs {
+	return KeyNs{
+		prefix: '${ns.prefix}/${key_escape(name)}'
+	}
+}
+
+// Build a namespaced key, e.g. ns_key('sprites', 'hero').
+// Same as key_ns(namespace).key(name).
+pub fn ns_key(namespace string, name string) []u8 {
+	return '${key_escape(namespace)}/${key_escape(name)}'.bytes()
+}
+
+// Escape '/' in a key segment so 'a/b' + 'c' and 'a' + 'b/c' stay distinct.
+fn key_escape(segment string) string {
+	return segment.replace('%', '%25').replace('/', '%2F')
+}
+
+// Pointer to the first element of data, or nil when it is empty so `&data[0]` can't panic.
+fn data_ptr[T](data []T) &T {
+	if data.len == 0 {
+		return unsafe { nil }
+	}
+	return &data[0]
+}
 
 // Graphics API.
 
+// FNV-1a hash used for mesh keys. Image and font keys are passed to the host as strings.
 fn hash_key(key []u8) u64 {
 	mut hash := u64(0xcbf29ce484222325)
 	for b in key {
@@ -108,6 +467,7 @@ pub fn graphics_set_size(width u32, height u32) {
 }
 
 // Set the current drawing color (RGBA).
+// Shapes are alpha blended over existing pixels; a = 255 is fully opaque.
 pub fn graphics_set_color(r u8, g u8, b u8, a u8) {
 	C.wasm96_graphics_set_color(u32(r), u32(g), u32(b), u32(a))
 }
@@ -117,6 +477,70 @@ pub fn graphics_background(r u8, g u8, b u8) {
 	C.wasm96_graphics_background(u32(r), u32(g), u32(b))
 }
 
+// Define up to 256 palette entries for palette mode.
+// Changing the palette recolors the whole frame when it is presented, enabling palette cycling.
+// An empty slice leaves the palette unchanged.
+pub fn graphics_palette_set(colors []Color) {
+	if colors.len == 0 {
+		return
+	}
+	count := if colors.len > 256 { 256 } else { colors.len }
+	C.wasm96_graphics_palette_set(&colors[0], u32(count))
+}
+
+// Enable or disable palette mode. In palette mode drawing uses palette indices and
+// the host expands them to colors at present time.
+pub fn graphics_palette_enable(enable bool) {
+	C.wasm96_graphics_palette_enable(if enable { 1 } else { 0 })
+}
+
+// Set the current drawing color to a palette index.
+pub fn graphics_set_color_index(index u8) {
+	C.wasm96_graphics_set_color_index(u32(index))
+}
+
+// Draw an image of palette indices (1 byte per pixel).
+pub fn graphics_image_indexed(x int, y int, w u32, h u32, data []u8) {
+	C.wasm96_graphics_image_indexed(x, y, w, h, &data[0], usize(data.len))
+}
+
+// Set the stroke width in pixels for lines, outlines, curves and polylines. Default is 1.
+pub fn graphics_set_stroke_width(px u32) {
+	C.wasm96_graphics_set_stroke_width(px)
+}
+
+// Set an 8x8 dither pattern for filled shapes. Each byte is one row with the most
+// significant bit on the left; pixels whose bit is 0 are left untouched.
+pub fn graphics_set_pattern(pattern [8]u8) {
+	C.wasm96_graphics_set_pattern(&pattern[0])
+}
+
+// Return to solid fills.
+pub fn graphics_pattern_reset() {
+	C.wasm96_graphics_pattern_reset()
+}
+
+// Clip all following drawing to a rectangle.
+pub fn graphics_clip(x int, y int, w u32, h u32) {
+	C.wasm96_graphics_clip(x, y, w, h)
+}
+
+// Remove the clipping rectangle.
+pub fn graphics_clip_reset() {
+	C.wasm96_graphics_clip_reset()
+}
+
+// Direct following drawing to a named layer. Layers are composited in ascending z order
+// when the frame is presented, so UI and world can be drawn in any order.
+pub fn graphics_layer_begin(key []u8, z int) {
+	C.wasm96_graphics_layer_begin(&key[0], usize(key.len), z)
+}
+
+// Return to drawing on the base layer (z = 0).
+pub fn graphics_layer_end() {
+	C.wasm96_graphics_layer_end()
+}
+
 // Draw a single pixel at (x, y).
 pub fn graphics_point(x int, y int) {
 	C.wasm96_graphics_point(x, y)
@@ -127,6 +551,16 @@ pub fn graphics_line(x1 int, y1 int, x2 int, y2 int) {
 	C.wasm96_graphics_line(x1, y1, x2, y2)
 }
 
+// Draw connected line segments through a list of points in one call.
+// points holds x, y pairs: [x1, y1, x2, y2, ...]. If closed, the last point joins the first.
+pub fn graphics_polyline(points []int, closed bool) {
+	if points.len < 4 {
+		return
+	}
+	flag := if closed { u32(1) } else { u32(0) }
+	C.wasm96_graphics_polyline(&points[0], u32(points.len / 2), flag)
+}
+
 // Draw a filled rectangle.
 pub fn graphics_rect(x int, y int, w u32, h u32) {
 	C.wasm96_graphics_rect(x, y, w, h)
@@ -147,6 +581,16 @@ pub fn graphics_circle_outline(x int, y int, r u32) {
 	C.wasm96_graphics_circle_outline(x, y, r)
 }
 
+// Draw a filled ellipse centered at (x, y) with radii rx and ry.
+pub fn graphics_ellipse(x int, y int, rx u32, ry u32) {
+	C.wasm96_graphics_ellipse(x, y, rx, ry)
+}
+
+// Draw an ellipse outline centered at (x, y) with radii rx and ry.
+pub fn graphics_ellipse_outline(x int, y int, rx u32, ry u32) {
+	C.wasm96_graphics_ellipse_outline(x, y, rx, ry)
+}
+
 // Draw an image/sprite.
 // data is a slice of RGBA bytes (4 bytes per pixel).
 pub fn graphics_image(x int, y int, w u32, h u32, data []u8) {
@@ -190,198 +634,2931 @@ pub fn graphics_pill_outline(x int, y int, w u32, h u32) {
 
 // Register an SVG resource under a string key.
 pub fn graphics_svg_register(key []u8, data []u8) bool {
-	return C.wasm96_graphics_svg_register(hash_key(key), &data[0], usize(data.len)) != 0
+	return C.wasm96_graphics_svg_register(&key[0], usize(key.len), &data[0], usize(data.len)) != 0
 }
 
 // Draw a registered SVG by key.
 pub fn graphics_svg_draw_key(key []u8, x int, y int, w u32, h u32) {
-	C.wasm96_graphics_svg_draw_key(hash_key(key), x, y, w, h)
+	C.wasm96_graphics_svg_draw_key(&key[0], usize(key.len), x, y, w, h)
+}
+
+// Draw a registered SVG rotated by angle radians about its center, with opacity 0.0 to 1.0.
+pub fn graphics_svg_draw_key_ex(key []u8, x int, y int, w u32, h u32, angle f32, opacity f32) {
+	C.wasm96_graphics_svg_draw_key_ex(&key[0], usize(key.len), x, y, w, h, angle, opacity)
 }
 
 // Unregister an SVG by key.
 pub fn graphics_svg_unregister(key []u8) {
-	C.wasm96_graphics_svg_unregister(hash_key(key))
+	C.wasm96_graphics_svg_unregister(&key[0], usize(key.len))
 }
 
 // Register a GIF resource under a string key.
 pub fn graphics_gif_register(key []u8, data []u8) bool {
-	return C.wasm96_graphics_gif_register(hash_key(key), &data[0], usize(data.len)) != 0
+	return C.wasm96_graphics_gif_register(&key[0], usize(key.len), &data[0], usize(data.len)) != 0
 }
 
 // Draw a registered GIF by key at natural size.
+// The animation frame follows the host's frame counter.
 pub fn graphics_gif_draw_key(key []u8, x int, y int) {
-	C.wasm96_graphics_gif_draw_key(hash_key(key), x, y)
+	C.wasm96_graphics_gif_draw_key(&key[0], usize(key.len), x, y)
 }
 
 // Draw a registered GIF by key scaled.
 pub fn graphics_gif_draw_key_scaled(key []u8, x int, y int, w u32, h u32) {
-	C.wasm96_graphics_gif_draw_key_scaled(hash_key(key), x, y, w, h)
+	C.wasm96_graphics_gif_draw_key_scaled(&key[0], usize(key.len), x, y, w, h)
+}
+
+// Draw a registered GIF scaled, showing the frame at time_ms into its animation.
+// Use this to drive animation from game state so replays and netplay stay deterministic.
+pub fn graphics_gif_draw_key_at(key []u8, x int, y int, w u32, h u32, time_ms u64) {
+	C.wasm96_graphics_gif_draw_key_at(&key[0], usize(key.len), x, y, w, h, time_ms)
 }
 
 // Unregister a GIF by key.
 pub fn graphics_gif_unregister(key []u8) {
-	C.wasm96_graphics_gif_unregister(hash_key(key))
+	C.wasm96_graphics_gif_unregister(&key[0], usize(key.len))
 }
 
 // Register a PNG resource under a string key.
+// Animated PNGs (APNG) are detected and play their frames like GIFs when drawn.
 pub fn graphics_png_register(key []u8, data []u8) bool {
-	return C.wasm96_graphics_png_register(hash_key(key), &data[0], usize(data.len)) != 0
+	return C.wasm96_graphics_png_register(&key[0], usize(key.len), &data[0], usize(data.len)) != 0
 }
 
 // Draw a registered PNG by key at natural size.
 pub fn graphics_png_draw_key(key []u8, x int, y int) {
-	C.wasm96_graphics_png_draw_key(hash_key(key), x, y)
+	C.wasm96_graphics_png_draw_key(&key[0], usize(key.len), x, y)
 }
 
 // Draw a registered PNG by key scaled.
 pub fn graphics_png_draw_key_scaled(key []u8, x int, y int, w u32, h u32) {
-	C.wasm96_graphics_png_draw_key_scaled(hash_key(key), x, y, w, h)
+	C.wasm96_graphics_png_draw_key_scaled(&key[0], usize(key.len), x, y, w, h)
+}
+
+// Draw the source rectangle (sx, sy, sw, sh) of a registered PNG at (dx, dy).
+pub fn graphics_png_draw_region(key []u8, sx int, sy int, sw u32, sh u32, dx int, dy int) {
+	C.wasm96_graphics_png_draw_region(&key[0], usize(key.len), sx, sy, sw, sh, dx, dy)
+}
+
+// Draw the source rectangle (sx, sy, sw, sh) of a registered PNG scaled into (dx, dy, dw, dh).
+pub fn graphics_png_draw_region_scaled(key []u8, sx int, sy int, sw u32, sh u32, dx int, dy int, dw u32, dh u32) {
+	C.wasm96_graphics_png_draw_region_scaled(&key[0], usize(key.len), sx, sy, sw, sh, dx, dy,
+		dw, dh)
+}
+
+// Draw a registered PNG as a 9-slice into (x, y, w, h). The corners given by the border
+// insets keep their size, the edges stretch along one axis and the center stretches both ways.
+pub fn graphics_png_draw_nine_slice(key []u8, x int, y int, w u32, h u32, left u32, top u32, right u32, bottom u32) {
+	C.wasm96_graphics_png_draw_nine_slice(&key[0], usize(key.len), x, y, w, h, left, top,
+		right, bottom)
 }
 
 // Unregister a PNG by key.
 pub fn graphics_png_unregister(key []u8) {
-	C.wasm96_graphics_png_unregister(hash_key(key))
+	C.wasm96_graphics_png_unregister(&key[0], usize(key.len))
+}
+
+// Register raw RGBA pixels (4 bytes per pixel) as a w x h sprite under a string key.
+// Use this to upload procedurally generated images once instead of calling graphics_image every frame.
+pub fn graphics_sprite_register(key []u8, w u32, h u32, rgba []u8) bool {
+	return C.wasm96_graphics_sprite_register(&key[0], usize(key.len), w, h, &rgba[0],
+		usize(rgba.len)) != 0
+}
+
+// Draw a registered sprite by key at natural size.
+pub fn graphics_sprite_draw_key(key []u8, x int, y int) {
+	C.wasm96_graphics_sprite_draw_key(&key[0], usize(key.len), x, y)
+}
+
+// Draw a registered sprite by key scaled.
+pub fn graphics_sprite_draw_key_scaled(key []u8, x int, y int, w u32, h u32) {
+	C.wasm96_graphics_sprite_draw_key_scaled(&key[0], usize(key.len), x, y, w, h)
+}
+
+// Unregister a sprite by key.
+pub fn graphics_sprite_unregister(key []u8) {
+	C.wasm96_graphics_sprite_unregister(&key[0], usize(key.len))
+}
+
+// Register a tilemap under a string key, using a registered PNG as its tileset.
+// Tiles are tile_w x tile_h and numbered left to right, top to bottom in the tileset.
+pub fn graphics_tilemap_register(key []u8, tileset_key []u8, tile_w u32, tile_h u32) bool {
+	return C.wasm96_graphics_tilemap_register(&key[0], usize(key.len), &tileset_key[0],
+		usize(tileset_key.len), tile_w, tile_h) != 0
+}
+
+// Upload a width x height grid of tile indices to a registered tilemap. -1 is an empty tile.
+pub fn graphics_tilemap_set_data(key []u8, width u32, height u32, tiles []int) {
+	C.wasm96_graphics_tilemap_set_data(&key[0], usize(key.len), width, height, data_ptr(tiles),
+		usize(tiles.len))
+}
+
+// Draw the visible part of a tilemap with world position (cam_x, cam_y) at the screen's top-left.
+pub fn graphics_tilemap_draw(key []u8, cam_x int, cam_y int) {
+	C.wasm96_graphics_tilemap_draw(&key[0], usize(key.len), cam_x, cam_y)
+}
+
+// Unregister a tilemap by key.
+pub fn graphics_tilemap_unregister(key []u8) {
+	C.wasm96_graphics_tilemap_unregister(&key[0], usize(key.len))
 }
 
 // Register a TTF font under a string key.
 pub fn graphics_font_register_ttf(key []u8, data []u8) bool {
-	return C.wasm96_graphics_font_register_ttf(hash_key(key), &data[0], usize(data.len)) != 0
+	return C.wasm96_graphics_font_register_ttf(&key[0], usize(key.len), &data[0], usize(data.len)) != 0
 }
 
 // Register a BDF font under a string key.
 pub fn graphics_font_register_bdf(key []u8, data []u8) bool {
-	return C.wasm96_graphics_font_register_bdf(hash_key(key), &data[0], usize(data.len)) != 0
+	return C.wasm96_graphics_font_register_bdf(&key[0], usize(key.len), &data[0], usize(data.len)) != 0
 }
 
 // Register a built-in Spleen font under a string key.
 pub fn graphics_font_register_spleen(key []u8, size u32) bool {
-	return C.wasm96_graphics_font_register_spleen(hash_key(key), size) != 0
+	return C.wasm96_graphics_font_register_spleen(&key[0], usize(key.len), size) != 0
+}
+
+// Set the pixel size used when drawing and measuring text with a TTF font.
+// The default is 16. Bitmap fonts (BDF and Spleen) ignore this.
+pub fn graphics_font_set_size(key []u8, px f32) {
+	C.wasm96_graphics_font_set_size(&key[0], usize(key.len), px)
 }
 
 // Unregister a font by key.
 pub fn graphics_font_unregister(key []u8) {
-	C.wasm96_graphics_font_unregister(hash_key(key))
+	C.wasm96_graphics_font_unregister(&key[0], usize(key.len))
 }
 
 // Draw text using a font referenced by key.
 pub fn graphics_text_key(x int, y int, font_key []u8, str []u8) {
-	C.wasm96_graphics_text_key(x, y, hash_key(font_key), &str[0], usize(str.len))
+	C.wasm96_graphics_text_key(x, y, &font_key[0], usize(font_key.len), &str[0], usize(str.len))
+}
+
+// Draw text anchored at (x, y) with the given alignment, e.g. .center and .middle to
+// center it on the point.
+pub fn graphics_text_key_aligned(x int, y int, font_key []u8, str []u8, h_align HAlign, v_align VAlign) {
+	C.wasm96_graphics_text_key_aligned(x, y, &font_key[0], usize(font_key.len), &str[0],
+		usize(str.len), u32(h_align), u32(v_align))
+}
+
+// Draw text word-wrapped to max_width, breaking lines at spaces and newlines, with
+// line_height pixels between lines. Returns the total height drawn.
+pub fn graphics_text_key_wrapped(x int, y int, max_width u32, line_height u32, font_key []u8, str []u8) u32 {
+	return C.wasm96_graphics_text_key_wrapped(x, y, max_width, line_height, &font_key[0],
+		usize(font_key.len), &str[0], usize(str.len))
 }
 
 // Measure text using a font referenced by key.
 pub fn graphics_text_measure_key(font_key []u8, str []u8) TextSize {
-	result := C.wasm96_graphics_text_measure_key(hash_key(font_key), &str[0], usize(str.len))
+	result := C.wasm96_graphics_text_measure_key(&font_key[0], usize(font_key.len), &str[0], usize(str.len))
 	return TextSize{
 		width: u32(result >> 32)
 		height: u32(result & 0xFFFFFFFF)
 	}
 }
 
-// 3D Graphics API.
+// Read the color of the pixel at (x, y) in the current frame.
+// Pixels outside the screen read as transparent black.
+pub fn graphics_get_pixel(x int, y int) Color {
+	packed := C.wasm96_graphics_get_pixel(x, y)
+	return Color{
+		r: u8(packed >> 16)
+		g: u8(packed >> 8)
+		b: u8(packed)
+		a: u8(packed >> 24)
+	}
+}
 
-// Enable or disable 3D rendering mode.
-pub fn graphics_set_3d(enable bool) {
-	C.wasm96_graphics_set_3d(if enable { 1 } else { 0 })
+// Read a rectangle of the current frame as RGBA bytes (4 bytes per pixel).
+// Returns an empty slice if the rectangle is empty or too large to fit in memory.
+pub fn graphics_read_rect(x int, y int, w u32, h u32) []u8 {
+	size := u64(w) * u64(h) * 4
+	if size == 0 || size > u64(max_i32) {
+		return []u8{}
+	}
+	mut buf := []u8{len: int(size)}
+	C.wasm96_graphics_read_rect(x, y, w, h, &buf[0], usize(buf.len))
+	return buf
 }
 
-// Set the camera position and target.
-pub fn graphics_camera_look_at(eye_x f32, eye_y f32, eye_z f32, target_x f32, target_y f32, target_z f32, up_x f32, up_y f32, up_z f32) {
-	C.wasm96_graphics_camera_look_at(eye_x, eye_y, eye_z, target_x, target_y, target_z, up_x, up_y, up_z)
+// Get drawing statistics for the current frame so far.
+pub fn graphics_stats() GraphicsStats {
+	mut stats := GraphicsStats{}
+	C.wasm96_graphics_stats(&stats)
+	return stats
 }
 
-// Set the camera perspective projection.
-pub fn graphics_camera_perspective(fovy f32, aspect f32, near f32, far f32) {
-	C.wasm96_graphics_camera_perspective(fovy, aspect, near, far)
+// Draw text wrapped to a maximum width, breaking on spaces and at newlines.
+// Returns the total height of the drawn lines. Wraps guest-side by measuring each line;
+// graphics_text_key_wrapped does the same work in a single host call.
+pub fn graphics_text_block(x int, y int, w u32, font_key []u8, text string) int {
+	line_height := int(graphics_text_measure_key(font_key, ' '.bytes()).height)
+	mut cy := y
+	for paragraph in text.split('\n') {
+		mut line := ''
+		for word in paragraph.split(' ') {
+			candidate := if line.len == 0 { word } else { line + ' ' + word }
+			if line.len > 0 && graphics_text_measure_key(font_key, candidate.bytes()).width > w {
+				graphics_text_key(x, cy, font_key, line.bytes())
+				cy += line_height
+				line = word
+			} else {
+				line = candidate
+			}
+		}
+		if line.len > 0 {
+			graphics_text_key(x, cy, font_key, line.bytes())
+		}
+		cy += line_height
+	}
+	return cy - y
 }
 
-// Create a mesh from raw vertex and index data.
-// vertices: [x, y, z, u, v, nx, ny, nz, ...]
-pub fn graphics_mesh_create(key []u8, vertices []f32, indices []u32) {
-	C.wasm96_graphics_mesh_create(hash_key(key), &vertices[0], usize(vertices.len), &indices[0], usize(indices.len))
+// Register an SVG, returning the host's error if it fails.
+pub fn try_graphics_svg_register(key []u8, data []u8) ! {
+	if !graphics_svg_register(key, data) {
+		return host_error('svg register failed')
+	}
 }
 
-// Create a mesh from OBJ file data.
-pub fn graphics_mesh_create_obj(key []u8, data []u8) {
-	C.wasm96_graphics_mesh_create_obj(hash_key(key), &data[0], usize(data.len))
+// Register a GIF, returning the host's error if it fails.
+pub fn try_graphics_gif_register(key []u8, data []u8) ! {
+	if !graphics_gif_register(key, data) {
+		return host_error('gif register failed')
+	}
 }
 
-// Create a mesh from STL file data.
-pub fn graphics_mesh_create_stl(key []u8, data []u8) {
-	C.wasm96_graphics_mesh_create_stl(hash_key(key), &data[0], usize(data.len))
+// Register a PNG, returning the host's error if it fails.
+pub fn try_graphics_png_register(key []u8, data []u8) ! {
+	if !graphics_png_register(key, data) {
+		return host_error('png register failed')
+	}
 }
 
-// Draw a mesh with transformation.
-pub fn graphics_mesh_draw(key []u8, pos_x f32, pos_y f32, pos_z f32, rot_x f32, rot_y f32, rot_z f32, scale_x f32, scale_y f32, scale_z f32) {
-	C.wasm96_graphics_mesh_draw(hash_key(key), pos_x, pos_y, pos_z, rot_x, rot_y, rot_z, scale_x, scale_y, scale_z)
+// Register a TTF font, returning the host's error if it fails.
+pub fn try_graphics_font_register_ttf(key []u8, data []u8) ! {
+	if !graphics_font_register_ttf(key, data) {
+		return host_error('ttf font register failed')
+	}
 }
 
-// Input API.
+// Register a BDF font, returning the host's error if it fails.
+pub fn try_graphics_font_register_bdf(key []u8, data []u8) ! {
+	if !graphics_font_register_bdf(key, data) {
+		return host_error('bdf font register failed')
+	}
+}
 
-// Returns true if the specified button is currently held down.
-pub fn input_is_button_down(port u32, btn Button) bool {
-	return C.wasm96_input_is_button_down(port, u32(btn)) != 0
+// Register a Spleen font, returning the host's error if it fails.
+pub fn try_graphics_font_register_spleen(key []u8, size u32) ! {
+	if !graphics_font_register_spleen(key, size) {
+		return host_error('spleen font register failed')
+	}
 }
 
-// Returns true if the specified key is currently held down.
-pub fn input_is_key_down(key u32) bool {
-	return C.wasm96_input_is_key_down(key) != 0
+// Batch API.
+
+enum BatchOp {
+	color
+	rect
+	rect_outline
+	circle
+	line
+	png
+	png_region
 }
 
-// Get current mouse X position.
-pub fn input_get_mouse_x() int {
-	return C.wasm96_input_get_mouse_x()
+struct BatchCmd {
+	op  BatchOp
+	key []u8
+	a   int
+	b   int
+	c   int
+	d   int
+	e   int
+	f   int
 }
 
-// Get current mouse Y position.
-pub fn input_get_mouse_y() int {
-	return C.wasm96_input_get_mouse_y()
+// A queue of draw calls submitted together by flush.
+// flush currently replays each call individually; it will switch to a single host call
+// once the batched draw ABI is available, without changes to guest code.
+pub struct Batch {
+mut:
+	cmds []BatchCmd
 }
 
-// Returns true if the specified mouse button is held down.
-// 0 = Left, 1 = Right, 2 = Middle.
-pub fn input_is_mouse_down(btn u32) bool {
-	return C.wasm96_input_is_mouse_down(btn) != 0
+// Queue a color change.
+pub fn (mut b Batch) set_color(c Color) {
+	b.cmds << BatchCmd{
+		op: .color
+		a: int(c.r)
+		b: int(c.g)
+		c: int(c.b)
+		d: int(c.a)
+	}
 }
 
-// Audio API.
+// Queue a filled rectangle.
+pub fn (mut b Batch) rect(x int, y int, w u32, h u32) {
+	b.cmds << BatchCmd{
+		op: .rect
+		a: x
+		b: y
+		c: int(w)
+		d: int(h)
+	}
+}
 
-// Initialize audio system.
-pub fn audio_init(sample_rate u32) u32 {
-	return C.wasm96_audio_init(sample_rate)
+// Queue a rectangle outline.
+pub fn (mut b Batch) rect_outline(x int, y int, w u32, h u32) {
+	b.cmds << BatchCmd{
+		op: .rect_outline
+		a: x
+		b: y
+		c: int(w)
+		d: int(h)
+	}
 }
 
-// Push a chunk of audio samples.
-// Samples are interleaved stereo (L, R, L, R...) signed 16-bit integers.
-pub fn audio_push_samples(samples []i16) {
-	C.wasm96_audio_push_samples(&samples[0], usize(samples.len))
+// Queue a filled circle.
+pub fn (mut b Batch) circle(x int, y int, r u32) {
+	b.cmds << BatchCmd{
+		op: .circle
+		a: x
+		b: y
+		c: int(r)
+	}
 }
 
-// Play a WAV file.
-// The WAV data is decoded and played as a one-shot audio channel.
-pub fn audio_play_wav(data []u8) {
-	C.wasm96_audio_play_wav(&data[0], usize(data.len))
+// Queue a line.
+pub fn (mut b Batch) line(x1 int, y1 int, x2 int, y2 int) {
+	b.cmds << BatchCmd{
+		op: .line
+		a: x1
+		b: y1
+		c: x2
+		d: y2
+	}
 }
 
-// Play a QOA file.
-// The QOA data is decoded and played as a looping audio channel.
-pub fn audio_play_qoa(data []u8) {
-	C.wasm96_audio_play_qoa(&data[0], usize(data.len))
+// Queue a registered PNG at natural size.
+pub fn (mut b Batch) png(key []u8, x int, y int) {
+	b.cmds << BatchCmd{
+		op: .png
+		key: key
+		a: x
+		b: y
+	}
 }
 
-// Play an XM file.
-// The XM data is decoded using xmrsplayer and played as a looping audio channel.
-pub fn audio_play_xm(data []u8) {
-	C.wasm96_audio_play_xm(&data[0], usize(data.len))
+// Queue a source rectangle of a registered PNG.
+pub fn (mut b Batch) png_region(key []u8, sx int, sy int, sw u32, sh u32, dx int, dy int) {
+	b.cmds << BatchCmd{
+		op: .png_region
+		key: key
+		a: sx
+		b: sy
+		c: int(sw)
+		d: int(sh)
+		e: dx
+		f: dy
+	}
 }
 
-// System API.
+// Number of queued draw calls.
+pub fn (b &Batch) len() int {
+	return b.cmds.len
+}
 
-// Log a message to the host console.
-pub fn system_log(message []u8) {
-	C.wasm96_system_log(&message[0], usize(message.len))
+// Submit all queued draw calls in order and clear the batch.
+pub fn (mut b Batch) flush() {
+	for cmd in b.cmds {
+		match cmd.op {
+			.color { graphics_set_color(u8(cmd.a), u8(cmd.b), u8(cmd.c), u8(cmd.d)) }
+			.rect { graphics_rect(cmd.a, cmd.b, u32(cmd.c), u32(cmd.d)) }
+			.rect_outline { graphics_rect_outline(cmd.a, cmd.b, u32(cmd.c), u32(cmd.d)) }
+			.circle { graphics_circle(cmd.a, cmd.b, u32(cmd.c)) }
+			.line { graphics_line(cmd.a, cmd.b, cmd.c, cmd.d) }
+			.png { graphics_png_draw_key(cmd.key, cmd.a, cmd.b) }
+			.png_region {
+				graphics_png_draw_region(cmd.key, cmd.a, cmd.b, u32(cmd.c), u32(cmd.d), cmd.e,
+					cmd.f)
+			}
+		}
+	}
+	b.cmds.clear()
 }
 
-// Get the number of milliseconds since the app started.
-pub fn system_millis() u64 {
-	return C.wasm96_system_millis()
+// Resource handle API.
+
+// A registered PNG. Call unregister when it is no longer needed.
+pub struct PngHandle {
+pub:
+	key []u8
+}
+
+// Register a PNG and return a handle, or none if the data could not be decoded.
+pub fn png_handle(key []u8, data []u8) ?PngHandle {
+	if !graphics_png_register(key, data) {
+		return none
+	}
+	return PngHandle{
+		key: key
+	}
+}
+
+// Draw the PNG at natural size.
+pub fn (p PngHandle) draw(x int, y int) {
+	graphics_png_draw_key(p.key, x, y)
+}
+
+// Draw the PNG scaled.
+pub fn (p PngHandle) draw_scaled(x int, y int, w u32, h u32) {
+	graphics_png_draw_key_scaled(p.key, x, y, w, h)
+}
+
+// Draw a source rectangle of the PNG.
+pub fn (p PngHandle) draw_region(sx int, sy int, sw u32, sh u32, dx int, dy int) {
+	graphics_png_draw_region(p.key, sx, sy, sw, sh, dx, dy)
+}
+
+// Draw a source rectangle of the PNG scaled.
+pub fn (p PngHandle) draw_region_scaled(sx int, sy int, sw u32, sh u32, dx int, dy int, dw u32, dh u32) {
+	graphics_png_draw_region_scaled(p.key, sx, sy, sw, sh, dx, dy, dw, dh)
+}
+
+// Draw the PNG as a 9-slice with the given border insets.
+pub fn (p PngHandle) draw_nine_slice(x int, y int, w u32, h u32, left u32, top u32, right u32, bottom u32) {
+	graphics_png_draw_nine_slice(p.key, x, y, w, h, left, top, right, bottom)
+}
+
+// Unregister the PNG.
+pub fn (p PngHandle) unregister() {
+	graphics_png_unregister(p.key)
+}
+
+// A registered GIF. Call unregister when it is no longer needed.
+pub struct GifHandle {
+pub:
+	key []u8
+}
+
+// Register a GIF and return a handle, or none if the data could not be decoded.
+pub fn gif_handle(key []u8, data []u8) ?GifHandle {
+	if !graphics_gif_register(key, data) {
+		return none
+	}
+	return GifHandle{
+		key: key
+	}
+}
+
+// Draw the GIF at natural size.
+pub fn (g GifHandle) draw(x int, y int) {
+	graphics_gif_draw_key(g.key, x, y)
+}
+
+// Draw the GIF scaled.
+pub fn (g GifHandle) draw_scaled(x int, y int, w u32, h u32) {
+	graphics_gif_draw_key_scaled(g.key, x, y, w, h)
+}
+
+// Draw the GIF scaled at a given animation time in milliseconds.
+pub fn (g GifHandle) draw_at(x int, y int, w u32, h u32, time_ms u64) {
+	graphics_gif_draw_key_at(g.key, x, y, w, h, time_ms)
+}
+
+// Unregister the GIF.
+pub fn (g GifHandle) unregister() {
+	graphics_gif_unregister(g.key)
+}
+
+// A registered SVG. Call unregister when it is no longer needed.
+pub struct SvgHandle {
+pub:
+	key []u8
+}
+
+// Register an SVG and return a handle, or none if the data could not be parsed.
+pub fn svg_handle(key []u8, data []u8) ?SvgHandle {
+	if !graphics_svg_register(key, data) {
+		return none
+	}
+	return SvgHandle{
+		key: key
+	}
+}
+
+// Draw the SVG into the given rectangle.
+pub fn (s SvgHandle) draw(x int, y int, w u32, h u32) {
+	graphics_svg_draw_key(s.key, x, y, w, h)
+}
+
+// Draw the SVG rotated and faded.
+pub fn (s SvgHandle) draw_ex(x int, y int, w u32, h u32, angle f32, opacity f32) {
+	graphics_svg_draw_key_ex(s.key, x, y, w, h, angle, opacity)
+}
+
+// Unregister the SVG.
+pub fn (s SvgHandle) unregister() {
+	graphics_svg_unregister(s.key)
+}
+
+// A registered font. Call unregister when it is no longer needed.
+pub struct FontHandle {
+pub:
+	key []u8
+}
+
+// Register a TTF font and return a handle, or none if the data could not be parsed.
+pub fn font_handle_ttf(key []u8, data []u8) ?FontHandle {
+	if !graphics_font_register_ttf(key, data) {
+		return none
+	}
+	return FontHandle{
+		key: key
+	}
+}
+
+// Register a BDF font and return a handle, or none if the data could not be parsed.
+pub fn font_handle_bdf(key []u8, data []u8) ?FontHandle {
+	if !graphics_font_register_bdf(key, data) {
+		return none
+	}
+	return FontHandle{
+		key: key
+	}
+}
+
+// Register a built-in Spleen font and return a handle, or none if the size is not available.
+pub fn font_handle_spleen(key []u8, size u32) ?FontHandle {
+	if !graphics_font_register_spleen(key, size) {
+		return none
+	}
+	return FontHandle{
+		key: key
+	}
+}
+
+// Draw text with this font.
+pub fn (f FontHandle) text(x int, y int, str []u8) {
+	graphics_text_key(x, y, f.key, str)
+}
+
+// Draw aligned text with this font.
+pub fn (f FontHandle) text_aligned(x int, y int, str []u8, h_align HAlign, v_align VAlign) {
+	graphics_text_key_aligned(x, y, f.key, str, h_align, v_align)
+}
+
+// Measure text with this font.
+pub fn (f FontHandle) measure(str []u8) TextSize {
+	return graphics_text_measure_key(f.key, str)
+}
+
+// Set the pixel size for a TTF font.
+pub fn (f FontHandle) set_size(px f32) {
+	graphics_font_set_size(f.key, px)
+}
+
+// Unregister the font.
+pub fn (f FontHandle) unregister() {
+	graphics_font_unregister(f.key)
+}
+
+// 3D Graphics API.
+
+// Enable or disable 3D rendering mode.
+pub fn graphics_set_3d(enable bool) {
+	C.wasm96_graphics_set_3d(if enable { 1 } else { 0 })
+}
+
+// Set the camera position and target.
+pub fn graphics_camera_look_at(eye_x f32, eye_y f32, eye_z f32, target_x f32, target_y f32, target_z f32, up_x f32, up_y f32, up_z f32) {
+	C.wasm96_graphics_camera_look_at(eye_x, eye_y, eye_z, target_x, target_y, target_z, up_x, up_y, up_z)
+}
+
+// Set the camera perspective projection.
+pub fn graphics_camera_perspective(fovy f32, aspect f32, near f32, far f32) {
+	C.wasm96_graphics_camera_perspective(fovy, aspect, near, far)
+}
+
+// Create a mesh from raw vertex and index data.
+// vertices: [x, y, z, u, v, nx, ny, nz, ...]
+pub fn graphics_mesh_create(key []u8, vertices []f32, indices []u32) {
+	C.wasm96_graphics_mesh_create(hash_key(key), &vertices[0], usize(vertices.len), &indices[0], usize(indices.len))
+}
+
+// Create a mesh from OBJ file data.
+pub fn graphics_mesh_create_obj(key []u8, data []u8) {
+	C.wasm96_graphics_mesh_create_obj(hash_key(key), &data[0], usize(data.len))
+}
+
+// Create a mesh from STL file data.
+pub fn graphics_mesh_create_stl(key []u8, data []u8) {
+	C.wasm96_graphics_mesh_create_stl(hash_key(key), &data[0], usize(data.len))
+}
+
+// Draw a mesh with transformation.
+pub fn graphics_mesh_draw(key []u8, pos_x f32, pos_y f32, pos_z f32, rot_x f32, rot_y f32, rot_z f32, scale_x f32, scale_y f32, scale_z f32) {
+	C.wasm96_graphics_mesh_draw(hash_key(key), pos_x, pos_y, pos_z, rot_x, rot_y, rot_z, scale_x, scale_y, scale_z)
+}
+
+// Color API.
+
+// An RGBA color.
+pub struct Color {
+pub:
+	r u8
+	g u8
+	b u8
+	a u8 = 255
+}
+
+// The 16-color PICO-8 palette.
+pub const palette_pico8 = [
+	Color{
+		r: 0x00
+		g: 0x00
+		b: 0x00
+	},
+	Color{
+		r: 0x1D
+		g: 0x2B
+		b: 0x53
+	},
+	Color{
+		r: 0x7E
+		g: 0x25
+		b: 0x53
+	},
+	Color{
+		r: 0x00
+		g: 0x87
+		b: 0x51
+	},
+	Color{
+		r: 0xAB
+		g: 0x52
+		b: 0x36
+	},
+	Color{
+		r: 0x5F
+		g: 0x57
+		b: 0x4F
+	},
+	Color{
+		r: 0xC2
+		g: 0xC3
+		b: 0xC7
+	},
+	Color{
+		r: 0xFF
+		g: 0xF1
+		b: 0xE8
+	},
+	Color{
+		r: 0xFF
+		g: 0x00
+		b: 0x4D
+	},
+	Color{
+		r: 0xFF
+		g: 0xA3
+		b: 0x00
+	},
+	Color{
+		r: 0xFF
+		g: 0xEC
+		b: 0x27
+	},
+	Color{
+		r: 0x00
+		g: 0xE4
+		b: 0x36
+	},
+	Color{
+		r: 0x29
+		g: 0xAD
+		b: 0xFF
+	},
+	Color{
+		r: 0x83
+		g: 0x76
+		b: 0x9C
+	},
+	Color{
+		r: 0xFF
+		g: 0x77
+		b: 0xA8
+	},
+	Color{
+		r: 0xFF
+		g: 0xCC
+		b: 0xAA
+	},
+]
+
+// The Sweetie-16 palette by GrafxKid.
+pub const palette_sweetie16 = [
+	Color{
+		r: 0x1A
+		g: 0x1C
+		b: 0x2C
+	},
+	Color{
+		r: 0x5D
+		g: 0x27
+		b: 0x5D
+	},
+	Color{
+		r: 0xB1
+		g: 0x3E
+		b: 0x53
+	},
+	Color{
+		r: 0xEF
+		g: 0x7D
+		b: 0x57
+	},
+	Color{
+		r: 0xFF
+		g: 0xCD
+		b: 0x75
+	},
+	Color{
+		r: 0xA7
+		g: 0xF0
+		b: 0x70
+	},
+	Color{
+		r: 0x38
+		g: 0xB7
+		b: 0x64
+	},
+	Color{
+		r: 0x25
+		g: 0x71
+		b: 0x79
+	},
+	Color{
+		r: 0x29
+		g: 0x36
+		b: 0x6F
+	},
+	Color{
+		r: 0x3B
+		g: 0x5D
+		b: 0xC9
+	},
+	Color{
+		r: 0x41
+		g: 0xA6
+		b: 0xF6
+	},
+	Color{
+		r: 0x73
+		g: 0xEF
+		b: 0xF7
+	},
+	Color{
+		r: 0xF4
+		g: 0xF4
+		b: 0xF4
+	},
+	Color{
+		r: 0x94
+		g: 0xB0
+		b: 0xC2
+	},
+	Color{
+		r: 0x56
+		g: 0x6C
+		b: 0x86
+	},
+	Color{
+		r: 0x33
+		g: 0x3C
+		b: 0x57
+	},
+]
+
+// Create an opaque color from RGB components.
+pub fn color_from_rgb(r u8, g u8, b u8) Color {
+	return Color{
+		r: r
+		g: g
+		b: b
+	}
+}
+
+// Create an opaque color from a 0xRRGGBB value.
+pub fn color_from_hex(hex u32) Color {
+	return Color{
+		r: u8(hex >> 16)
+		g: u8(hex >> 8)
+		b: u8(hex)
+	}
+}
+
+// Create an opaque color from hue (degrees), saturation and value (0.0 to 1.0).
+pub fn color_from_hsv(h f32, s f32, v f32) Color {
+	mut hh := h - 360 * f32(int(h / 360))
+	if hh < 0 {
+		hh += 360
+	}
+	c := v * s
+	sector := hh / 60
+	mut m2 := sector - 2 * f32(int(sector / 2)) - 1
+	if m2 < 0 {
+		m2 = -m2
+	}
+	x := c * (1 - m2)
+	mut r, mut g, mut b := f32(0), f32(0), f32(0)
+	match int(sector) {
+		0 { r, g = c, x }
+		1 { r, g = x, c }
+		2 { g, b = c, x }
+		3 { g, b = x, c }
+		4 { r, b = x, c }
+		else { r, b = c, x }
+	}
+	m := v - c
+	return Color{
+		r: u8((r + m) * 255 + 0.5)
+		g: u8((g + m) * 255 + 0.5)
+		b: u8((b + m) * 255 + 0.5)
+	}
+}
+
+// Linearly interpolate towards other; t is clamped to 0.0..1.0.
+pub fn (c Color) lerp(other Color, t f32) Color {
+	tt := if t < 0 { f32(0) } else if t > 1 { f32(1) } else { t }
+	return Color{
+		r: u8(f32(c.r) + (f32(other.r) - f32(c.r)) * tt)
+		g: u8(f32(c.g) + (f32(other.g) - f32(c.g)) * tt)
+		b: u8(f32(c.b) + (f32(other.b) - f32(c.b)) * tt)
+		a: u8(f32(c.a) + (f32(other.a) - f32(c.a)) * tt)
+	}
+}
+
+// Return the same color with a different alpha.
+pub fn (c Color) with_alpha(a u8) Color {
+	return Color{
+		...c
+		a: a
+	}
+}
+
+// Set the current drawing color.
+pub fn graphics_color(c Color) {
+	graphics_set_color(c.r, c.g, c.b, c.a)
+}
+
+// Clear the screen with a color; alpha is ignored.
+pub fn graphics_clear(c Color) {
+	graphics_background(c.r, c.g, c.b)
+}
+
+// Sprite API.
+
+// A sprite sheet: a registered PNG cut into equal frames, numbered left to right, top to bottom.
+pub struct Sprite {
+pub:
+	key     []u8
+	frame_w u32
+	frame_h u32
+	columns u32 = 1
+}
+
+// Draw one frame of the sprite sheet at (x, y). Negative frames draw nothing.
+pub fn (s &Sprite) draw(frame int, x int, y int) {
+	if frame < 0 || s.columns == 0 {
+		return
+	}
+	col := u32(frame) % s.columns
+	row := u32(frame) / s.columns
+	graphics_png_draw_region(s.key, int(col * s.frame_w), int(row * s.frame_h), s.frame_w,
+		s.frame_h, x, y)
+}
+
+// Draw one frame of the sprite sheet scaled to w x h.
+pub fn (s &Sprite) draw_scaled(frame int, x int, y int, w u32, h u32) {
+	if frame < 0 || s.columns == 0 {
+		return
+	}
+	col := u32(frame) % s.columns
+	row := u32(frame) / s.columns
+	graphics_png_draw_region_scaled(s.key, int(col * s.frame_w), int(row * s.frame_h),
+		s.frame_w, s.frame_h, x, y, w, h)
+}
+
+// A frame animation: a list of sprite frame indices played at a fixed rate.
+pub struct Animation {
+pub mut:
+	frames  []int
+	fps     f32 = 12
+	looping bool = true
+mut:
+	time    f32
+	playing bool = true
+}
+
+// Restart the animation from its first frame.
+pub fn (mut a Animation) play() {
+	a.time = 0
+	a.playing = true
+}
+
+// Stop the animation on its current frame.
+pub fn (mut a Animation) stop() {
+	a.playing = false
+}
+
+// Advance the animation by dt seconds.
+pub fn (mut a Animation) update(dt f32) {
+	if a.playing {
+		a.time += dt
+	}
+}
+
+// Returns true once a non-looping animation has shown its last frame.
+pub fn (a &Animation) finished() bool {
+	return !a.looping && int(a.time * a.fps) >= a.frames.len
+}
+
+// Current sprite frame index.
+pub fn (a &Animation) frame() int {
+	if a.frames.len == 0 {
+		return 0
+	}
+	mut i := int(a.time * a.fps)
+	if a.looping {
+		i %= a.frames.len
+	} else if i >= a.frames.len {
+		i = a.frames.len - 1
+	}
+	return a.frames[i]
+}
+
+// Draw the current frame of the animation using a sprite sheet.
+pub fn (a &Animation) draw(sprite &Sprite, x int, y int) {
+	sprite.draw(a.frame(), x, y)
+}
+
+// Tilemap API.
+
+// A grid of tile indices drawn from a sprite sheet. Tile index -1 is empty.
+pub struct Tilemap {
+pub:
+	width   int
+	height  int
+	tileset Sprite
+pub mut:
+	tiles []int
+mut:
+	uploaded_key []u8
+}
+
+// Create an empty tilemap of width x height tiles.
+pub fn tilemap_new(tileset Sprite, width int, height int) Tilemap {
+	return Tilemap{
+		width: width
+		height: height
+		tileset: tileset
+		tiles: []int{len: width * height, init: -1}
+	}
+}
+
+// Get the tile at (x, y), or -1 if out of bounds.
+pub fn (t &Tilemap) get(x int, y int) int {
+	if x < 0 || y < 0 || x >= t.width || y >= t.height {
+		return -1
+	}
+	return t.tiles[y * t.width + x]
+}
+
+// Set the tile at (x, y). Out-of-bounds writes are ignored.
+pub fn (mut t Tilemap) set(x int, y int, tile int) {
+	if x < 0 || y < 0 || x >= t.width || y >= t.height {
+		return
+	}
+	t.tiles[y * t.width + x] = tile
+}
+
+// Draw the tiles visible in a view_w x view_h screen whose top-left corner is at
+// world position (cam_x, cam_y).
+pub fn (t &Tilemap) draw(cam_x int, cam_y int, view_w int, view_h int) {
+	tw := int(t.tileset.frame_w)
+	th := int(t.tileset.frame_h)
+	if tw == 0 || th == 0 {
+		return
+	}
+	x0 := if cam_x > 0 { cam_x / tw } else { 0 }
+	y0 := if cam_y > 0 { cam_y / th } else { 0 }
+	mut x1 := (cam_x + view_w + tw - 1) / tw
+	mut y1 := (cam_y + view_h + th - 1) / th
+	if x1 > t.width {
+		x1 = t.width
+	}
+	if y1 > t.height {
+		y1 = t.height
+	}
+	for ty in y0 .. y1 {
+		for tx in x0 .. x1 {
+			tile := t.tiles[ty * t.width + tx]
+			if tile >= 0 {
+				t.tileset.draw(tile, tx * tw - cam_x, ty * th - cam_y)
+			}
+		}
+	}
+}
+
+// Register the tilemap with the host and upload its tiles, so it can be drawn in one
+// call with graphics_tilemap_draw. Upload again after changing tiles; the tilemap is
+// only registered the first time it is uploaded under a key.
+pub fn (mut t Tilemap) upload(key []u8) bool {
+	if t.uploaded_key != key {
+		if !graphics_tilemap_register(key, t.tileset.key, t.tileset.frame_w, t.tileset.frame_h) {
+			return false
+		}
+		t.uploaded_key = key.clone()
+	}
+	graphics_tilemap_set_data(key, u32(t.width), u32(t.height), t.tiles)
+	return true
+}
+
+// Fixed-point API.
+
+// Q16.16 fixed-point number for deterministic simulation.
+// Arithmetic on Fx gives identical results on every platform, unlike f32.
+pub struct Fx {
+pub:
+	raw i32
+}
+
+// One in Q16.16.
+pub const fx_one = Fx{
+	raw: 65536
+}
+
+// Pi in Q16.16.
+pub const fx_pi = Fx{
+	raw: 205887
+}
+
+// sin over one full turn in 256 steps, in Q16.16.
+const fx_sin_table = [
+	0, 1608, 3216, 4821, 6424, 8022, 9616, 11204,
+	12785, 14359, 15924, 17479, 19024, 20557, 22078, 23586,
+	25080, 26558, 28020, 29466, 30893, 32303, 33692, 35062,
+	36410, 37736, 39040, 40320, 41576, 42806, 44011, 45190,
+	46341, 47464, 48559, 49624, 50660, 51665, 52639, 53581,
+	54491, 55368, 56212, 57022, 57798, 58538, 59244, 59914,
+	60547, 61145, 61705, 62228, 62714, 63162, 63572, 63944,
+	64277, 64571, 64827, 65043, 65220, 65358, 65457, 65516,
+	65536, 65516, 65457, 65358, 65220, 65043, 64827, 64571,
+	64277, 63944, 63572, 63162, 62714, 62228, 61705, 61145,
+	60547, 59914, 59244, 58538, 57798, 57022, 56212, 55368,
+	54491, 53581, 52639, 51665, 50660, 49624, 48559, 47464,
+	46341, 45190, 44011, 42806, 41576, 40320, 39040, 37736,
+	36410, 35062, 33692, 32303, 30893, 29466, 28020, 26558,
+	25080, 23586, 22078, 20557, 19024, 17479, 15924, 14359,
+	12785, 11204, 9616, 8022, 6424, 4821, 3216, 1608,
+	0, -1608, -3216, -4821, -6424, -8022, -9616, -11204,
+	-12785, -14359, -15924, -17479, -19024, -20557, -22078, -23586,
+	-25080, -26558, -28020, -29466, -30893, -32303, -33692, -35062,
+	-36410, -37736, -39040, -40320, -41576, -42806, -44011, -45190,
+	-46341, -47464, -48559, -49624, -50660, -51665, -52639, -53581,
+	-54491, -55368, -56212, -57022, -57798, -58538, -59244, -59914,
+	-60547, -61145, -61705, -62228, -62714, -63162, -63572, -63944,
+	-64277, -64571, -64827, -65043, -65220, -65358, -65457, -65516,
+	-65536, -65516, -65457, -65358, -65220, -65043, -64827, -64571,
+	-64277, -63944, -63572, -63162, -62714, -62228, -61705, -61145,
+	-60547, -59914, -59244, -58538, -57798, -57022, -56212, -55368,
+	-54491, -53581, -52639, -51665, -50660, -49624, -48559, -47464,
+	-46341, -45190, -44011, -42806, -41576, -40320, -39040, -37736,
+	-36410, -35062, -33692, -32303, -30893, -29466, -28020, -26558,
+	-25080, -23586, -22078, -20557, -19024, -17479, -15924, -14359,
+	-12785, -11204, -9616, -8022, -6424, -4821, -3216, -1608,
+]!
+
+// Create a fixed-point value from an integer.
+pub fn fx_from_int(i int) Fx {
+	return Fx{
+		raw: i32(i) << 16
+	}
+}
+
+// Create a fixed-point value from a float. Only use this for constants and setup,
+// never inside deterministic simulation.
+pub fn fx_from_f32(f f32) Fx {
+	return Fx{
+		raw: i32(f * 65536)
+	}
+}
+
+// Integer part, rounded towards negative infinity.
+pub fn (a Fx) to_int() int {
+	return int(a.raw >> 16)
+}
+
+// Convert to a float, for rendering.
+pub fn (a Fx) to_f32() f32 {
+	return f32(a.raw) / 65536
+}
+
+// Add two fixed-point values.
+pub fn (a Fx) + (b Fx) Fx {
+	return Fx{
+		raw: a.raw + b.raw
+	}
+}
+
+// Subtract two fixed-point values.
+pub fn (a Fx) - (b Fx) Fx {
+	return Fx{
+		raw: a.raw - b.raw
+	}
+}
+
+// Multiply two fixed-point values.
+pub fn (a Fx) * (b Fx) Fx {
+	return Fx{
+		raw: i32((i64(a.raw) * i64(b.raw)) >> 16)
+	}
+}
+
+// Divide two fixed-point values. Division by zero returns zero.
+pub fn (a Fx) / (b Fx) Fx {
+	if b.raw == 0 {
+		return Fx{}
+	}
+	return Fx{
+		raw: i32((i64(a.raw) << 16) / i64(b.raw))
+	}
+}
+
+// Compare two fixed-point values.
+pub fn (a Fx) < (b Fx) bool {
+	return a.raw < b.raw
+}
+
+// Absolute value.
+pub fn (a Fx) abs() Fx {
+	if a.raw < 0 {
+		return Fx{
+			raw: -a.raw
+		}
+	}
+	return a
+}
+
+// Square root. Negative values return zero.
+pub fn (a Fx) sqrt() Fx {
+	if a.raw <= 0 {
+		return Fx{}
+	}
+	n := u64(a.raw) << 16
+	mut x := n
+	mut y := (x + 1) / 2
+	for y < x {
+		x = y
+		y = (x + n / x) / 2
+	}
+	return Fx{
+		raw: i32(x)
+	}
+}
+
+// Sine of an angle in radians, using a lookup table with linear interpolation.
+pub fn fx_sin(angle Fx) Fx {
+	// Map radians to 1/256ths of a turn in Q16.16: angle * 256 / (2 * pi).
+	turns := (i64(angle.raw) * 256 * 65536) / (2 * i64(fx_pi.raw))
+	index := int((turns >> 16) & 255)
+	frac := i64(turns & 0xFFFF)
+	a := i64(fx_sin_table[index])
+	b := i64(fx_sin_table[(index + 1) & 255])
+	return Fx{
+		raw: i32(a + (((b - a) * frac) >> 16))
+	}
+}
+
+// Cosine of an angle in radians.
+pub fn fx_cos(angle Fx) Fx {
+	return fx_sin(angle + Fx{
+		raw: fx_pi.raw / 2
+	})
+}
+
+// Math API.
+
+// A 2D vector.
+pub struct Vec2 {
+pub mut:
+	x f32
+	y f32
+}
+
+// An axis-aligned rectangle.
+pub struct Rect {
+pub mut:
+	x f32
+	y f32
+	w f32
+	h f32
+}
+
+// A circle.
+pub struct Circle {
+pub mut:
+	center Vec2
+	radius f32
+}
+
+// Create a vector.
+pub fn vec2(x f32, y f32) Vec2 {
+	return Vec2{
+		x: x
+		y: y
+	}
+}
+
+// Add two vectors.
+pub fn (a Vec2) + (b Vec2) Vec2 {
+	return Vec2{
+		x: a.x + b.x
+		y: a.y + b.y
+	}
+}
+
+// Subtract two vectors.
+pub fn (a Vec2) - (b Vec2) Vec2 {
+	return Vec2{
+		x: a.x - b.x
+		y: a.y - b.y
+	}
+}
+
+// Multiply by a scalar.
+pub fn (v Vec2) scale(s f32) Vec2 {
+	return Vec2{
+		x: v.x * s
+		y: v.y * s
+	}
+}
+
+// Dot product.
+pub fn (a Vec2) dot(b Vec2) f32 {
+	return a.x * b.x + a.y * b.y
+}
+
+// Squared length, cheaper than length for comparisons.
+pub fn (v Vec2) length_squared() f32 {
+	return v.x * v.x + v.y * v.y
+}
+
+// Length of the vector.
+pub fn (v Vec2) length() f32 {
+	return math.sqrtf(v.length_squared())
+}
+
+// Unit vector in the same direction, or zero for a zero vector.
+pub fn (v Vec2) normalize() Vec2 {
+	len := v.length()
+	if len == 0 {
+		return Vec2{}
+	}
+	return v.scale(1 / len)
+}
+
+// Distance between two points.
+pub fn (a Vec2) distance(b Vec2) f32 {
+	return (a - b).length()
+}
+
+// Linearly interpolate towards b.
+pub fn (a Vec2) lerp(b Vec2, t f32) Vec2 {
+	return a + (b - a).scale(t)
+}
+
+// Create a rectangle.
+pub fn rect(x f32, y f32, w f32, h f32) Rect {
+	return Rect{
+		x: x
+		y: y
+		w: w
+		h: h
+	}
+}
+
+// Center point of the rectangle.
+pub fn (r Rect) center() Vec2 {
+	return Vec2{
+		x: r.x + r.w / 2
+		y: r.y + r.h / 2
+	}
+}
+
+// Returns true if the point lies inside the rectangle.
+pub fn (r Rect) contains(p Vec2) bool {
+	return p.x >= r.x && p.x < r.x + r.w && p.y >= r.y && p.y < r.y + r.h
+}
+
+// Returns true if the rectangles overlap.
+pub fn (r Rect) intersects(o Rect) bool {
+	return r.x < o.x + o.w && o.x < r.x + r.w && r.y < o.y + o.h && o.y < r.y + r.h
+}
+
+// Create a circle.
+pub fn circle(x f32, y f32, radius f32) Circle {
+	return Circle{
+		center: Vec2{
+			x: x
+			y: y
+		}
+		radius: radius
+	}
+}
+
+// Returns true if the point lies inside the circle.
+pub fn (c Circle) contains(p Vec2) bool {
+	return (p - c.center).length_squared() <= c.radius * c.radius
+}
+
+// Returns true if the circles overlap.
+pub fn (c Circle) intersects(o Circle) bool {
+	r := c.radius + o.radius
+	return (o.center - c.center).length_squared() <= r * r
+}
+
+// Collision API.
+
+// Contact found by a swept collision test.
+pub struct Hit {
+pub:
+	// Fraction of the movement (0.0 to 1.0) at which contact happens.
+	time f32
+	// Surface normal of the side that was hit.
+	normal Vec2
+}
+
+// Returns true if the circle overlaps the rectangle.
+pub fn (c Circle) intersects_rect(r Rect) bool {
+	mut px := c.center.x
+	mut py := c.center.y
+	if px < r.x {
+		px = r.x
+	} else if px > r.x + r.w {
+		px = r.x + r.w
+	}
+	if py < r.y {
+		py = r.y
+	} else if py > r.y + r.h {
+		py = r.y + r.h
+	}
+	dx := c.center.x - px
+	dy := c.center.y - py
+	return dx * dx + dy * dy <= c.radius * c.radius
+}
+
+// Sweep rectangle a along vel against the static rectangle b.
+// Returns none if a does not touch b during the movement or already overlaps it.
+pub fn collide_swept_rect(a Rect, vel Vec2, b Rect) ?Hit {
+	far := f32(1e30)
+	mut tx_entry, mut tx_exit := -far, far
+	if vel.x > 0 {
+		tx_entry = (b.x - (a.x + a.w)) / vel.x
+		tx_exit = (b.x + b.w - a.x) / vel.x
+	} else if vel.x < 0 {
+		tx_entry = (b.x + b.w - a.x) / vel.x
+		tx_exit = (b.x - (a.x + a.w)) / vel.x
+	} else if a.x + a.w <= b.x || a.x >= b.x + b.w {
+		return none
+	}
+	mut ty_entry, mut ty_exit := -far, far
+	if vel.y > 0 {
+		ty_entry = (b.y - (a.y + a.h)) / vel.y
+		ty_exit = (b.y + b.h - a.y) / vel.y
+	} else if vel.y < 0 {
+		ty_entry = (b.y + b.h - a.y) / vel.y
+		ty_exit = (b.y - (a.y + a.h)) / vel.y
+	} else if a.y + a.h <= b.y || a.y >= b.y + b.h {
+		return none
+	}
+	entry := if tx_entry > ty_entry { tx_entry } else { ty_entry }
+	exit := if tx_exit < ty_exit { tx_exit } else { ty_exit }
+	if entry > exit || entry < 0 || entry > 1 {
+		return none
+	}
+	normal := if tx_entry > ty_entry {
+		vec2(if vel.x > 0 { f32(-1) } else { f32(1) }, 0)
+	} else {
+		vec2(0, if vel.y > 0 { f32(-1) } else { f32(1) })
+	}
+	return Hit{
+		time: entry
+		normal: normal
+	}
+}
+
+// Debug API.
+
+// On-screen diagnostic panel using the built-in Spleen font.
+// Call watch for each value during a frame, then draw at the end of draw.
+pub struct DebugOverlay {
+pub mut:
+	visible bool = true
+	x       int  = 4
+	y       int  = 4
+mut:
+	lines       []string
+	font_ready  bool
+	last_millis u64
+	fps_avg     f32
+}
+
+const debug_font_key = 'wasm96.debug'.bytes()
+
+// Show or hide the overlay.
+pub fn (mut d DebugOverlay) toggle() {
+	d.visible = !d.visible
+}
+
+// Add a named value to this frame's panel.
+pub fn (mut d DebugOverlay) watch(name string, value string) {
+	d.lines << '${name}: ${value}'
+}
+
+// Frames per second, averaged over recent frames.
+pub fn (d &DebugOverlay) fps() f32 {
+	return d.fps_avg
+}
+
+// Draw the panel and clear the watched values for the next frame.
+pub fn (mut d DebugOverlay) draw() {
+	now := system_millis()
+	if d.last_millis != 0 && now > d.last_millis {
+		d.fps_avg = d.fps_avg * 0.9 + (1000 / f32(now - d.last_millis)) * 0.1
+	}
+	d.last_millis = now
+	if d.visible {
+		if !d.font_ready {
+			d.font_ready = graphics_font_register_spleen(debug_font_key, 8)
+		}
+		mut all := ['fps: ${int(d.fps_avg)}']
+		all << d.lines
+		mut w := u32(0)
+		for line in all {
+			size := graphics_text_measure_key(debug_font_key, line.bytes())
+			if size.width > w {
+				w = size.width
+			}
+		}
+		line_h := 10
+		graphics_set_color(0, 0, 0, 160)
+		graphics_rect(d.x, d.y, w + 8, u32(all.len * line_h + 6))
+		graphics_set_color(255, 255, 255, 255)
+		for i, line in all {
+			graphics_text_key(d.x + 4, d.y + 3 + i * line_h, debug_font_key, line.bytes())
+		}
+	}
+	d.lines.clear()
+}
+
+// Camera API.
+
+// A 2D camera centered on pos, with zoom and screen shake.
+pub struct Camera2D {
+pub mut:
+	pos Vec2
+	// Screen-pixels per world unit.
+	zoom f32 = 1.0
+	// Screen size in pixels, normally the size passed to graphics_set_size.
+	viewport Vec2
+	// Shake intensity decay per second.
+	shake_decay f32 = 2.0
+	// Largest shake offset in pixels at full intensity.
+	shake_max f32 = 8.0
+mut:
+	shake  f32
+	offset Vec2
+	rng    Rng = rng_new(7)
+}
+
+// Create a camera for a screen of the given size.
+pub fn camera2d(width f32, height f32) Camera2D {
+	return Camera2D{
+		viewport: vec2(width, height)
+	}
+}
+
+// Add screen shake intensity (0.0 to 1.0).
+pub fn (mut c Camera2D) add_shake(amount f32) {
+	c.shake = clamp01(c.shake + amount)
+}
+
+// Advance shake by dt seconds. Call once per frame.
+pub fn (mut c Camera2D) update(dt f32) {
+	c.shake -= c.shake_decay * dt
+	if c.shake <= 0 {
+		c.shake = 0
+		c.offset = Vec2{}
+		return
+	}
+	strength := c.shake * c.shake * c.shake_max
+	c.offset = vec2(c.rng.range_f32(-strength, strength), c.rng.range_f32(-strength, strength))
+}
+
+// Convert a world position to screen pixels.
+pub fn (c &Camera2D) world_to_screen(p Vec2) Vec2 {
+	return (p - c.pos).scale(c.zoom) + c.viewport.scale(0.5) + c.offset
+}
+
+// Convert a screen position (e.g. the mouse) to world coordinates.
+pub fn (c &Camera2D) screen_to_world(p Vec2) Vec2 {
+	return (p - c.offset - c.viewport.scale(0.5)).scale(1 / c.zoom) + c.pos
+}
+
+// The visible world area, for culling.
+pub fn (c &Camera2D) view_rect() Rect {
+	w := c.viewport.x / c.zoom
+	h := c.viewport.y / c.zoom
+	return rect(c.pos.x - w / 2, c.pos.y - h / 2, w, h)
+}
+
+// Returns true if a world-space rectangle is at least partly visible.
+pub fn (c &Camera2D) is_visible(r Rect) bool {
+	return c.view_rect().intersects(r)
+}
+
+// Transition API.
+
+// A screen transition drawn over the frame. A closing transition covers the screen;
+// an opening one reveals it. Call update each frame and draw after the scene.
+pub struct Transition {
+pub:
+	kind     TransitionKind
+	duration f32 = 0.5
+	closing  bool = true
+	color    Color
+mut:
+	time f32
+}
+
+// Create a transition of the given kind and duration in seconds.
+pub fn transition_new(kind TransitionKind, duration f32, closing bool) Transition {
+	return Transition{
+		kind: kind
+		duration: duration
+		closing: closing
+	}
+}
+
+// Advance the transition by dt seconds.
+pub fn (mut t Transition) update(dt f32) {
+	t.time += dt
+	if t.time > t.duration {
+		t.time = t.duration
+	}
+}
+
+// Returns true once the transition has finished.
+pub fn (t &Transition) done() bool {
+	return t.time >= t.duration
+}
+
+// How much of the screen is covered, from 0.0 to 1.0.
+pub fn (t &Transition) coverage() f32 {
+	p := if t.duration > 0 { t.time / t.duration } else { f32(1) }
+	return if t.closing { p } else { 1 - p }
+}
+
+// Draw the transition over a screen of the given size.
+pub fn (t &Transition) draw(width u32, height u32) {
+	c := t.coverage()
+	if c <= 0 {
+		return
+	}
+	match t.kind {
+		.fade {
+			graphics_color(t.color.with_alpha(u8(f32(t.color.a) * c)))
+			graphics_rect(0, 0, width, height)
+		}
+		.wipe {
+			graphics_color(t.color)
+			graphics_rect(0, 0, u32(f32(width) * c), height)
+		}
+		.iris {
+			// A circle growing from the center until it covers the corners.
+			radius := vec2(f32(width), f32(height)).length() / 2
+			graphics_color(t.color)
+			graphics_circle(int(width / 2), int(height / 2), u32(radius * c))
+		}
+	}
+}
+
+// UI API.
+
+// Immediate-mode UI context. Call begin once per frame before drawing widgets.
+// font_key must name a font registered with one of the graphics_font_register functions.
+pub struct Ui {
+pub mut:
+	font_key   []u8
+	text_color Color = Color{r: 255, g: 255, b: 255}
+	fill       Color = Color{r: 64, g: 64, b: 72}
+	hover      Color = Color{r: 96, g: 96, b: 108}
+	accent     Color = Color{r: 41, g: 173, b: 255}
+mut:
+	mouse    Vec2
+	down     bool
+	was_down bool
+}
+
+// Poll the mouse for this frame.
+pub fn (mut ui Ui) begin() {
+	ui.was_down = ui.down
+	ui.down = input_is_mouse_down(0)
+	ui.mouse = vec2(f32(input_get_mouse_x()), f32(input_get_mouse_y()))
+}
+
+fn (ui &Ui) clicked(r Rect) bool {
+	return ui.down && !ui.was_down && r.contains(ui.mouse)
+}
+
+fn (ui &Ui) fill_rect(r Rect, c Color) {
+	graphics_color(c)
+	graphics_rect(int(r.x), int(r.y), u32(r.w), u32(r.h))
+}
+
+// Draw text with its top-left corner at (x, y).
+pub fn (ui &Ui) label(text string, x int, y int) {
+	if text.len == 0 {
+		return
+	}
+	graphics_color(ui.text_color)
+	graphics_text_key(x, y, ui.font_key, text.bytes())
+}
+
+// Draw a button; returns true on the frame it is clicked.
+pub fn (mut ui Ui) button(label string, r Rect) bool {
+	ui.fill_rect(r, if r.contains(ui.mouse) { ui.hover } else { ui.fill })
+	if label.len > 0 {
+		size := graphics_text_measure_key(ui.font_key, label.bytes())
+		ui.label(label, int(r.x + (r.w - f32(size.width)) / 2), int(r.y + (r.h - f32(size.height)) / 2))
+	}
+	return ui.clicked(r)
+}
+
+// Draw a checkbox with a label to its right; returns the new checked state.
+pub fn (mut ui Ui) checkbox(label string, r Rect, checked bool) bool {
+	mut value := checked
+	if ui.clicked(r) {
+		value = !value
+	}
+	ui.fill_rect(r, if r.contains(ui.mouse) { ui.hover } else { ui.fill })
+	if value {
+		inset := r.h / 4
+		ui.fill_rect(rect(r.x + inset, r.y + inset, r.w - inset * 2, r.h - inset * 2), ui.accent)
+	}
+	ui.label(label, int(r.x + r.w + 4), int(r.y))
+	return value
+}
+
+// Draw a horizontal slider; returns the new value, clamped to min..max.
+pub fn (mut ui Ui) slider(r Rect, value f32, min f32, max f32) f32 {
+	mut v := value
+	if ui.down && r.contains(ui.mouse) && r.w > 0 {
+		v = min + (max - min) * (ui.mouse.x - r.x) / r.w
+	}
+	if v < min {
+		v = min
+	} else if v > max {
+		v = max
+	}
+	ui.fill_rect(r, ui.fill)
+	if max > min {
+		ui.fill_rect(rect(r.x, r.y, r.w * (v - min) / (max - min), r.h), ui.accent)
+	}
+	return v
+}
+
+// Particle API.
+
+struct Particle {
+mut:
+	pos  Vec2
+	vel  Vec2
+	age  f32
+	life f32
+}
+
+// A particle emitter. Configure the public fields, then call update and draw each frame.
+pub struct Emitter {
+pub mut:
+	pos Vec2
+	// Particles spawned per second; 0 for bursts only.
+	rate            f32 = 30
+	lifetime        f32 = 1.0
+	lifetime_spread f32
+	// Emission direction and spread, in radians.
+	direction     f32
+	spread        f32 = 6.2831855
+	speed_min     f32 = 20
+	speed_max     f32 = 60
+	gravity       Vec2
+	color_start   Color = Color{r: 255, g: 255, b: 255}
+	color_end     Color = Color{r: 255, g: 255, b: 255, a: 0}
+	size_start    f32   = 4
+	size_end      f32
+	max_particles int = 256
+	rng           Rng = rng_new(1)
+mut:
+	particles []Particle
+	spawn_acc f32
+}
+
+// Spawn n particles immediately.
+pub fn (mut e Emitter) emit(n int) {
+	for _ in 0 .. n {
+		if e.particles.len >= e.max_particles {
+			return
+		}
+		angle := e.direction + e.rng.range_f32(-e.spread / 2, e.spread / 2)
+		speed := e.rng.range_f32(e.speed_min, e.speed_max)
+		mut life := e.lifetime + e.rng.range_f32(-e.lifetime_spread, e.lifetime_spread)
+		// Keep life positive so age / life stays finite when spread >= lifetime.
+		if life < 0.001 {
+			life = 0.001
+		}
+		e.particles << Particle{
+			pos: e.pos
+			vel: vec2(math.cosf(angle) * speed, math.sinf(angle) * speed)
+			life: life
+		}
+	}
+}
+
+// Spawn particles at the configured rate and advance existing ones by dt seconds.
+pub fn (mut e Emitter) update(dt f32) {
+	e.spawn_acc += e.rate * dt
+	n := int(e.spawn_acc)
+	e.spawn_acc -= f32(n)
+	e.emit(n)
+	mut alive := 0
+	for i in 0 .. e.particles.len {
+		mut p := e.particles[i]
+		p.age += dt
+		if p.age >= p.life {
+			continue
+		}
+		p.vel = p.vel + e.gravity.scale(dt)
+		p.pos = p.pos + p.vel.scale(dt)
+		e.particles[alive] = p
+		alive++
+	}
+	e.particles.trim(alive)
+}
+
+// Number of live particles.
+pub fn (e &Emitter) count() int {
+	return e.particles.len
+}
+
+// Draw all live particles as squares, interpolating color and size over their life.
+// The particles are submitted together through a Batch.
+pub fn (e &Emitter) draw() {
+	mut b := Batch{}
+	e.draw_to(mut b)
+	b.flush()
+}
+
+// Queue all live particles on b without flushing, so several emitters can share one batch.
+pub fn (e &Emitter) draw_to(mut b Batch) {
+	for p in e.particles {
+		t := p.age / p.life
+		size := e.size_start + (e.size_end - e.size_start) * t
+		if size < 1 {
+			continue
+		}
+		b.set_color(e.color_start.lerp(e.color_end, t))
+		half := size / 2
+		b.rect(int(p.pos.x - half), int(p.pos.y - half), u32(size), u32(size))
+	}
+}
+
+// A single-line text input field fed by input_next_char.
+// Call update once per frame; it drains the character queue.
+pub struct TextField {
+pub mut:
+	max_len int = 32
+mut:
+	chars          []rune
+	cursor         int
+	left_was_down  bool
+	right_was_down bool
+}
+
+const key_left = u32(276)
+const key_right = u32(275)
+
+// Process typed characters and cursor keys. Returns true on the frame Enter is pressed.
+pub fn (mut f TextField) update() bool {
+	mut submitted := false
+	for {
+		ch := input_next_char()
+		if ch == 0 {
+			break
+		}
+		match u32(ch) {
+			8 {
+				if f.cursor > 0 {
+					f.chars.delete(f.cursor - 1)
+					f.cursor--
+				}
+			}
+			127 {
+				if f.cursor < f.chars.len {
+					f.chars.delete(f.cursor)
+				}
+			}
+			13 {
+				submitted = true
+			}
+			else {
+				if u32(ch) >= 32 && f.chars.len < f.max_len {
+					f.chars.insert(f.cursor, ch)
+					f.cursor++
+				}
+			}
+		}
+	}
+	left := input_is_key_down(key_left)
+	right := input_is_key_down(key_right)
+	if left && !f.left_was_down && f.cursor > 0 {
+		f.cursor--
+	}
+	if right && !f.right_was_down && f.cursor < f.chars.len {
+		f.cursor++
+	}
+	f.left_was_down = left
+	f.right_was_down = right
+	return submitted
+}
+
+// Current contents.
+pub fn (f &TextField) text() string {
+	return f.chars.string()
+}
+
+// Replace the contents and move the cursor to the end.
+pub fn (mut f TextField) set_text(text string) {
+	f.chars = text.runes()
+	if f.chars.len > f.max_len {
+		f.chars.trim(f.max_len)
+	}
+	f.cursor = f.chars.len
+}
+
+// Draw the text with a cursor bar, top-left at (x, y).
+pub fn (f &TextField) draw(x int, y int, font_key []u8) {
+	text := f.text()
+	if text.len > 0 {
+		graphics_text_key(x, y, font_key, text.bytes())
+	}
+	before := f.chars[..f.cursor].string()
+	height := graphics_text_measure_key(font_key, ' '.bytes()).height
+	mut cx := x
+	if before.len > 0 {
+		cx += int(graphics_text_measure_key(font_key, before.bytes()).width)
+	}
+	graphics_rect(cx, y, 1, height)
+}
+
+// Input API.
+
+// Returns true if the specified button is currently held down.
+pub fn input_is_button_down(port u32, btn Button) bool {
+	return C.wasm96_input_is_button_down(port, u32(btn)) != 0
+}
+
+// Returns true if the specified key is currently held down.
+pub fn input_is_key_down(key u32) bool {
+	return C.wasm96_input_is_key_down(key) != 0
+}
+
+// Get current mouse X position.
+pub fn input_get_mouse_x() int {
+	return C.wasm96_input_get_mouse_x()
+}
+
+// Get current mouse Y position.
+pub fn input_get_mouse_y() int {
+	return C.wasm96_input_get_mouse_y()
+}
+
+// Returns true if the specified mouse button is held down.
+// 0 = Left, 1 = Right, 2 = Middle.
+pub fn input_is_mouse_down(btn u32) bool {
+	return C.wasm96_input_is_mouse_down(btn) != 0
+}
+
+// Pop the next typed character from the host's text input queue.
+// Returns 0 when the queue is empty. Backspace is 8, Enter is 13 and Delete is 127.
+pub fn input_next_char() rune {
+	return rune(C.wasm96_input_next_char())
+}
+
+// Get an analog stick axis (0 = X, 1 = Y) in the range -32768..32767.
+pub fn input_get_analog(port u32, stick Stick, axis u32) i32 {
+	return C.wasm96_input_get_analog(port, u32(stick), axis)
+}
+
+// Set rumble strength for a port's strong and weak motors (0 to 65535).
+pub fn input_set_rumble(port u32, strong u16, weak u16) {
+	C.wasm96_input_set_rumble(port, u32(strong), u32(weak))
+}
+
+// A player's gamepad.
+pub struct Gamepad {
+pub:
+	port u32
+}
+
+// Get the gamepad for a player port (0 for player 1).
+pub fn gamepad(port u32) Gamepad {
+	return Gamepad{
+		port: port
+	}
+}
+
+// Returns true if the button is held down.
+pub fn (g Gamepad) is_down(btn Button) bool {
+	return input_is_button_down(g.port, btn)
+}
+
+// Stick position with each axis in -1.0..1.0.
+pub fn (g Gamepad) stick(stick Stick) Vec2 {
+	x := f32(input_get_analog(g.port, stick, 0)) / 32768
+	y := f32(input_get_analog(g.port, stick, 1)) / 32768
+	return vec2(x, y)
+}
+
+// Left stick position with each axis in -1.0..1.0.
+pub fn (g Gamepad) left_stick() Vec2 {
+	return g.stick(.left)
+}
+
+// Right stick position with each axis in -1.0..1.0.
+pub fn (g Gamepad) right_stick() Vec2 {
+	return g.stick(.right)
+}
+
+// Set rumble strength (0.0 to 1.0) for the strong and weak motors.
+pub fn (g Gamepad) rumble(strong f32, weak f32) {
+	input_set_rumble(g.port, u16(clamp01(strong) * 65535), u16(clamp01(weak) * 65535))
+}
+
+fn clamp01(v f32) f32 {
+	return if v < 0 { f32(0) } else if v > 1 { f32(1) } else { v }
+}
+
+// Edge-detecting input state for this gamepad.
+pub fn (g Gamepad) state() InputState {
+	return input_state(g.port)
+}
+
+// Joypad state for one port with edge detection.
+// Call update once per frame before querying it.
+pub struct InputState {
+pub:
+	port u32
+mut:
+	current  u32
+	previous u32
+	held     [16]u32
+}
+
+// Create input state tracking for a joypad port.
+pub fn input_state(port u32) InputState {
+	return InputState{
+		port: port
+	}
+}
+
+// Poll all buttons for this frame.
+pub fn (mut s InputState) update() {
+	s.previous = s.current
+	s.current = 0
+	for i in 0 .. 16 {
+		if C.wasm96_input_is_button_down(s.port, u32(i)) != 0 {
+			s.current |= u32(1) << i
+			s.held[i]++
+		} else {
+			s.held[i] = 0
+		}
+	}
+}
+
+// Returns true while the button is held down.
+pub fn (s &InputState) pressed(btn Button) bool {
+	return s.current & (u32(1) << u32(btn)) != 0
+}
+
+// Returns true only on the frame the button went down.
+pub fn (s &InputState) just_pressed(btn Button) bool {
+	mask := u32(1) << u32(btn)
+	return s.current & mask != 0 && s.previous & mask == 0
+}
+
+// Returns true only on the frame the button was released.
+pub fn (s &InputState) just_released(btn Button) bool {
+	mask := u32(1) << u32(btn)
+	return s.current & mask == 0 && s.previous & mask != 0
+}
+
+// Number of consecutive frames the button has been held, or 0 if it is up.
+pub fn (s &InputState) held_for(btn Button) u32 {
+	return s.held[u32(btn)]
+}
+
+// Audio API.
+
+// Initialize audio system.
+pub fn audio_init(sample_rate u32) u32 {
+	return C.wasm96_audio_init(sample_rate)
+}
+
+// Push a chunk of audio samples.
+// Samples are interleaved stereo (L, R, L, R...) signed 16-bit integers.
+pub fn audio_push_samples(samples []i16) {
+	C.wasm96_audio_push_samples(&samples[0], usize(samples.len))
+}
+
+// Play a WAV file.
+// The WAV data is decoded and played as a one-shot audio channel.
+pub fn audio_play_wav(data []u8) {
+	C.wasm96_audio_play_wav(&data[0], usize(data.len))
+}
+
+// Play a QOA file.
+// The QOA data is decoded and played as a looping audio channel.
+pub fn audio_play_qoa(data []u8) {
+	C.wasm96_audio_play_qoa(&data[0], usize(data.len))
+}
+
+// Play an XM file.
+// The XM data is decoded using xmrsplayer and played as a looping audio channel.
+pub fn audio_play_xm(data []u8) {
+	C.wasm96_audio_play_xm(&data[0], usize(data.len))
+}
+
+// Play audio data on a new channel and return its handle, or 0 if it could not be decoded.
+pub fn audio_channel_play(format AudioFormat, data []u8, looping bool) u32 {
+	flag := if looping { u32(1) } else { u32(0) }
+	return C.wasm96_audio_channel_play(u32(format), &data[0], usize(data.len), flag)
+}
+
+// Stop a playing channel.
+pub fn audio_channel_stop(channel u32) {
+	C.wasm96_audio_channel_stop(channel)
+}
+
+// Set a channel's volume (0.0 to 1.0).
+pub fn audio_channel_set_volume(channel u32, volume f32) {
+	C.wasm96_audio_channel_set_volume(channel, volume)
+}
+
+// A looping music track with volume control and fades.
+// Call update once per frame while fading.
+pub struct Music {
+pub:
+	data   []u8
+	format AudioFormat
+mut:
+	channel       u32
+	volume        f32 = 1.0
+	fade_from     f32
+	fade_target   f32
+	fade_time     f32
+	fade_duration f32
+}
+
+// Create a music track from WAV, QOA or XM data.
+pub fn music_new(format AudioFormat, data []u8) Music {
+	return Music{
+		data: data
+		format: format
+	}
+}
+
+// Start playing from the beginning, restarting if already playing.
+pub fn (mut m Music) play() {
+	m.stop()
+	m.channel = audio_channel_play(m.format, m.data, true)
+	audio_channel_set_volume(m.channel, m.volume)
+}
+
+// Stop playing.
+pub fn (mut m Music) stop() {
+	if m.channel != 0 {
+		audio_channel_stop(m.channel)
+		m.channel = 0
+	}
+	m.fade_duration = 0
+}
+
+// Returns true while the track is playing.
+pub fn (m &Music) is_playing() bool {
+	return m.channel != 0
+}
+
+// Set the volume (0.0 to 1.0), cancelling any fade.
+pub fn (mut m Music) set_volume(volume f32) {
+	m.volume = volume
+	m.fade_duration = 0
+	if m.channel != 0 {
+		audio_channel_set_volume(m.channel, volume)
+	}
+}
+
+// Fade to a volume over the given number of seconds.
+// Fading to 0 stops the track when the fade completes.
+pub fn (mut m Music) fade_to(volume f32, seconds f32) {
+	if seconds <= 0 {
+		m.set_volume(volume)
+		return
+	}
+	m.fade_from = m.volume
+	m.fade_target = volume
+	m.fade_time = 0
+	m.fade_duration = seconds
+}
+
+// Advance any fade in progress by dt seconds.
+pub fn (mut m Music) update(dt f32) {
+	if m.fade_duration <= 0 {
+		return
+	}
+	m.fade_time += dt
+	if m.fade_time >= m.fade_duration {
+		m.set_volume(m.fade_target)
+		if m.volume == 0 {
+			m.stop()
+		}
+		return
+	}
+	m.volume = m.fade_from + (m.fade_target - m.fade_from) * m.fade_time / m.fade_duration
+	if m.channel != 0 {
+		audio_channel_set_volume(m.channel, m.volume)
+	}
+}
+
+// A one-shot sound effect.
+pub struct Sfx {
+pub:
+	data []u8
+pub mut:
+	volume f32 = 1.0
+}
+
+// Create a sound effect from WAV data.
+pub fn sfx_new(data []u8) Sfx {
+	return Sfx{
+		data: data
+	}
+}
+
+// Play the effect once and return its channel handle.
+pub fn (s &Sfx) play() u32 {
+	channel := audio_channel_play(.wav, s.data, false)
+	if channel != 0 && s.volume != 1.0 {
+		audio_channel_set_volume(channel, s.volume)
+	}
+	return channel
+}
+
+// Storage API.
+
+// Convert a host status code into a StorageError. Codes this SDK doesn't know are
+// reported as io_error rather than cast into the enum.
+fn storage_check(status u32) ! {
+	match status {
+		0 {}
+		1 {
+			return StorageError{
+				status: .quota_exceeded
+			}
+		}
+		2 {
+			return StorageError{
+				status: .invalid_key
+			}
+		}
+		else {
+			return StorageError{
+				status: .io_error
+			}
+		}
+	}
+}
+
+// Save a value under a string key.
+// Values are persisted to a per-game file in the frontend's save directory.
+// Returns a StorageError if the value exceeds the per-game quota, the key is not
+// valid UTF-8, or the value could not be written.
+pub fn storage_save(key []u8, data []u8) ! {
+	storage_check(C.wasm96_storage_save(&key[0], usize(key.len), data_ptr(data), usize(data.len)))!
+}
+
+// Load a value previously saved under a string key.
+// Returns none if nothing has been saved under the key.
+pub fn storage_load(key []u8) ?[]u8 {
+	result := C.wasm96_storage_load(&key[0], usize(key.len))
+	if result == 0 {
+		return none
+	}
+	ptr := unsafe { &u8(usize(result >> 32)) }
+	return unsafe { ptr.vbytes(int(result & 0xFFFFFFFF)) }
+}
+
+// Build a storage key scoped to a namespace, e.g. storage_key('slot1', 'player').
+pub fn storage_key(namespace string, key string) []u8 {
+	return ns_key(namespace, key)
+}
+
+// Save a plain value under a key.
+// T must be a number or a struct without pointers, strings or arrays.
+pub fn storage_save_value[T](key []u8, value T) ! {
+	v := value
+	storage_save(key, unsafe { (&u8(&v)).vbytes(int(sizeof(T))) })!
+}
+
+// Load a plain value saved with storage_save_value.
+// Returns none if nothing is saved under the key or the stored size does not match T.
+pub fn storage_load_value[T](key []u8) ?T {
+	data := storage_load(key)?
+	if data.len != int(sizeof(T)) {
+		return none
+	}
+	return unsafe { *(&T(&data[0])) }
+}
+
+// Save a plain value together with a format version number.
+// Load it with storage_load_versioned.
+pub fn storage_save_versioned[T](key []u8, version u32, value T) ! {
+	v := value
+	mut data := []u8{len: 4, init: u8(version >> (u32(index) * 8))}
+	data << unsafe { (&u8(&v)).vbytes(int(sizeof(T))) }
+	storage_save(key, data)!
+}
+
+// Load a value saved with storage_save_versioned.
+// If it was saved with a different version, migrate receives the stored version and
+// payload and returns the value converted to the current format, or none to discard it.
+pub fn storage_load_versioned[T](key []u8, version u32, migrate fn (u32, []u8) ?T) ?T {
+	data := storage_load(key)?
+	if data.len < 4 {
+		return none
+	}
+	stored := u32(data[0]) | (u32(data[1]) << 8) | (u32(data[2]) << 16) | (u32(data[3]) << 24)
+	payload := data[4..]
+	if stored != version {
+		return migrate(stored, payload)
+	}
+	if payload.len != int(sizeof(T)) {
+		return none
+	}
+	return unsafe { *(&T(&payload[0])) }
+}
+
+// Get the size in bytes of the value stored under a key.
+// Returns 0 if nothing has been saved under the key.
+pub fn storage_size(key []u8) u64 {
+	return C.wasm96_storage_size(&key[0], usize(key.len))
+}
+
+// Read part of a stored value, starting at offset, into dst.
+// Returns the number of bytes read, which is less than dst.len at the end of the value.
+pub fn storage_load_chunk(key []u8, offset u64, mut dst []u8) int {
+	return int(C.wasm96_storage_load_chunk(&key[0], usize(key.len), offset, data_ptr(dst), usize(dst.len)))
+}
+
+// Delete the value stored under a string key.
+pub fn storage_delete(key []u8) {
+	C.wasm96_storage_delete(&key[0], usize(key.len))
+}
+
+// Returns true if a value is stored under the key.
+pub fn storage_exists(key []u8) bool {
+	return C.wasm96_storage_exists(&key[0], usize(key.len)) != 0
+}
+
+// List all stored keys starting with prefix.
+// An empty prefix lists every key.
+pub fn storage_list(prefix []u8) []string {
+	mut buf := []u8{len: 256}
+	// The host writes newline-separated keys and returns the total length.
+	mut n := int(C.wasm96_storage_list(data_ptr(prefix), usize(prefix.len), &buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_storage_list(data_ptr(prefix), usize(prefix.len), &buf[0], usize(buf.len)))
+	}
+	if n == 0 {
+		return []string{}
+	}
+	return buf[..n].bytestr().split('\n')
+}
+
+// Begin a storage transaction.
+// Saves and deletes made until storage_commit are written to disk together.
+pub fn storage_begin() {
+	C.wasm96_storage_begin()
+}
+
+// Commit the current storage transaction.
+// Returns a StorageError if the changes could not be written; the previous save file is kept intact.
+pub fn storage_commit() ! {
+	storage_check(C.wasm96_storage_commit())!
+}
+
+// Save a setting shared by all wasm96 games, such as global volume or a colorblind mode.
+// Settings are stored per core rather than per game.
+pub fn settings_save(key []u8, data []u8) ! {
+	storage_check(C.wasm96_settings_save(&key[0], usize(key.len), data_ptr(data), usize(data.len)))!
+}
+
+// Load a shared setting saved with settings_save.
+// Returns none if the setting has never been saved.
+pub fn settings_load(key []u8) ?[]u8 {
+	result := C.wasm96_settings_load(&key[0], usize(key.len))
+	if result == 0 {
+		return none
+	}
+	ptr := unsafe { &u8(usize(result >> 32)) }
+	return unsafe { ptr.vbytes(int(result & 0xFFFFFFFF)) }
+}
+
+// Asset API.
+
+// Read a file from the cartridge's assets/ tree.
+// Returns none if the file does not exist or the ROM is not a .w96 cartridge.
+pub fn asset_read(path []u8) ?[]u8 {
+	result := C.wasm96_asset_read(&path[0], usize(path.len))
+	if result == 0 {
+		return none
+	}
+	ptr := unsafe { &u8(usize(result >> 32)) }
+	return unsafe { ptr.vbytes(int(result & 0xFFFFFFFF)) }
+}
+
+// Register a PNG from the cartridge under a string key, without copying it into guest memory.
+pub fn asset_register_png(key []u8, path []u8) bool {
+	return C.wasm96_asset_register_png(&key[0], usize(key.len), &path[0], usize(path.len)) != 0
+}
+
+// Play a WAV file from the cartridge.
+// Returns false if the file could not be read.
+pub fn asset_play_wav(path []u8) bool {
+	data := asset_read(path) or { return false }
+	audio_play_wav(data)
+	return true
+}
+
+// An asset to register under a key.
+// Set data to embedded bytes (e.g. $embed_file('hero.png').to_bytes()), path to load
+// from the cartridge instead, or size for Spleen fonts.
+pub struct Asset {
+pub:
+	kind AssetKind
+	key  string
+	data []u8
+	path string
+	size u32
+}
+
+// Register a list of assets, typically during setup.
+// Returns an error naming the first asset that failed to register.
+pub fn register_assets(assets []Asset) ! {
+	for asset in assets {
+		if asset.key.len == 0 {
+			return error('asset has an empty key')
+		}
+		key := asset.key.bytes()
+		if asset.kind == .spleen {
+			if !graphics_font_register_spleen(key, asset.size) {
+				return error('failed to register asset ${asset.key}')
+			}
+			continue
+		}
+		if asset.kind == .png && asset.path.len > 0 {
+			if !asset_register_png(key, asset.path.bytes()) {
+				return error('failed to register asset ${asset.key}')
+			}
+			continue
+		}
+		data := if asset.path.len > 0 {
+			asset_read(asset.path.bytes()) or { return error('missing asset ${asset.path}') }
+		} else {
+			asset.data
+		}
+		if data.len == 0 {
+			return error('asset ${asset.key} has no data or path')
+		}
+		ok := match asset.kind {
+			.png { graphics_png_register(key, data) }
+			.gif { graphics_gif_register(key, data) }
+			.svg { graphics_svg_register(key, data) }
+			.ttf { graphics_font_register_ttf(key, data) }
+			.bdf { graphics_font_register_bdf(key, data) }
+			.spleen { false } // handled above
+		}
+		if !ok {
+			return error('failed to register asset ${asset.key}')
+		}
+	}
+}
+
+// Read a field from the ROM's wasm96.meta custom section.
+// Text fields are UTF-8 and the icon is PNG data. Returns an empty slice if the field is missing.
+pub fn cart_meta(field MetaField) []u8 {
+	mut buf := []u8{len: 256}
+	mut n := int(C.wasm96_cart_meta(u32(field), &buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_cart_meta(u32(field), &buf[0], usize(buf.len)))
+	}
+	return buf[..n]
+}
+
+// Network API.
+// Networking is off by default and must be enabled in the core options; while it is off,
+// opening sockets fails. All calls are non-blocking and rate limited by the host.
+
+// Open a UDP socket bound to a local port (0 for any). Returns none if networking is disabled.
+pub fn net_udp_open(port u32) ?u32 {
+	socket := C.wasm96_net_udp_open(port)
+	if socket == 0 {
+		return none
+	}
+	return socket
+}
+
+// Send a datagram to an address such as '203.0.113.5:7777'. Returns the number of bytes sent,
+// or 0 if addr is empty.
+pub fn net_udp_send(socket u32, addr []u8, data []u8) int {
+	if addr.len == 0 {
+		return 0
+	}
+	return int(C.wasm96_net_udp_send(socket, &addr[0], usize(addr.len), data_ptr(data), usize(data.len)))
+}
+
+// Receive one pending datagram into buf. Returns its length, or 0 if none is waiting.
+// Datagrams longer than buf are truncated to buf.len.
+pub fn net_udp_recv(socket u32, mut buf []u8) int {
+	n := int(C.wasm96_net_udp_recv(socket, data_ptr(buf), usize(buf.len)))
+	return if n > buf.len { buf.len } else { n }
+}
+
+// Close a UDP socket.
+pub fn net_udp_close(socket u32) {
+	C.wasm96_net_udp_close(socket)
+}
+
+// Start a TCP connection to an address such as 'example.com:80'.
+// Returns none if networking is disabled or the address is invalid.
+pub fn net_tcp_connect(addr []u8) ?u32 {
+	conn := C.wasm96_net_tcp_connect(&addr[0], usize(addr.len))
+	if conn == 0 {
+		return none
+	}
+	return conn
+}
+
+// Queue data on a TCP connection. Returns the number of bytes accepted.
+pub fn net_tcp_send(conn u32, data []u8) int {
+	return int(C.wasm96_net_tcp_send(conn, data_ptr(data), usize(data.len)))
+}
+
+// Read received data into buf. Returns the number of bytes read, or 0 if none is waiting.
+// The count never exceeds buf.len.
+pub fn net_tcp_recv(conn u32, mut buf []u8) int {
+	n := int(C.wasm96_net_tcp_recv(conn, data_ptr(buf), usize(buf.len)))
+	return if n > buf.len { buf.len } else { n }
+}
+
+// Close a TCP connection.
+pub fn net_tcp_close(conn u32) {
+	C.wasm96_net_tcp_close(conn)
+}
+
+// Start an HTTP GET request on a host thread. Returns a request id to poll with
+// net_http_status, or none if networking is disabled.
+pub fn net_http_get(url []u8) ?u32 {
+	request := C.wasm96_net_http_get(&url[0], usize(url.len))
+	if request == 0 {
+		return none
+	}
+	return request
+}
+
+// Poll a request: 0 while pending, -1 if it failed, otherwise the HTTP status code.
+pub fn net_http_status(request u32) int {
+	return int(C.wasm96_net_http_status(request))
+}
+
+// Get the body of a completed request.
+pub fn net_http_body(request u32) []u8 {
+	len := int(C.wasm96_net_http_body_len(request))
+	if len == 0 {
+		return []u8{}
+	}
+	mut buf := []u8{len: len}
+	n := int(C.wasm96_net_http_read_body(request, &buf[0], usize(buf.len)))
+	return buf[..n]
+}
+
+// Release a request and its body. Also cancels a request that is still pending.
+pub fn net_http_free(request u32) {
+	C.wasm96_net_http_free(request)
+}
+
+// Host a peer-to-peer session through the host's relay.
+// Returns the session code other players enter to join, or none if networking is disabled.
+// Calling it while already hosting returns the open session's code.
+pub fn net_peer_host() ?string {
+	mut buf := []u8{len: 32}
+	mut n := int(C.wasm96_net_peer_host(&buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_net_peer_host(&buf[0], usize(buf.len)))
+	}
+	if n == 0 {
+		return none
+	}
+	return buf[..n].bytestr()
+}
+
+// Join a session by its code. Returns false if the session could not be joined.
+pub fn net_peer_join(code []u8) bool {
+	return C.wasm96_net_peer_join(&code[0], usize(code.len)) != 0
+}
+
+// Leave the current session.
+pub fn net_peer_leave() {
+	C.wasm96_net_peer_leave()
+}
+
+// Number of other peers connected to the session.
+pub fn net_peer_count() u32 {
+	return C.wasm96_net_peer_count()
+}
+
+// Send a message to a peer. Reliable messages are delivered in order; unreliable ones
+// may be dropped but arrive sooner, which suits rollback input packets.
+// Returns false if the message could not be queued.
+pub fn net_peer_send(peer u32, reliable bool, data []u8) bool {
+	flag := if reliable { u32(1) } else { u32(0) }
+	return C.wasm96_net_peer_send(peer, flag, data_ptr(data), usize(data.len)) != 0
+}
+
+// Receive the next pending message, or none if the queue is empty.
+// Messages longer than 1200 bytes are truncated.
+pub fn net_peer_recv() ?PeerMessage {
+	mut buf := []u8{len: 1200}
+	result := C.wasm96_net_peer_recv(&buf[0], usize(buf.len))
+	if result == 0 {
+		return none
+	}
+	mut n := int(result & 0xFFFFFFFF)
+	if n > buf.len {
+		n = buf.len
+	}
+	return PeerMessage{
+		peer: u32(result >> 32)
+		data: buf[..n]
+	}
+}
+
+// Options API.
+
+// Declare a game option shown in the frontend's core options menu.
+// Call during setup. The first value is the default.
+pub fn option_define(key []u8, label []u8, values []string) {
+	joined := values.join('|').bytes()
+	C.wasm96_option_define(&key[0], usize(key.len), &label[0], usize(label.len), data_ptr(joined),
+		usize(joined.len))
+}
+
+// Get the current value of an option declared with option_define.
+// Returns an empty string for unknown keys.
+pub fn option_get(key []u8) string {
+	mut buf := []u8{len: 64}
+	mut n := int(C.wasm96_option_get(&key[0], usize(key.len), &buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_option_get(&key[0], usize(key.len), &buf[0], usize(buf.len)))
+	}
+	return buf[..n].bytestr()
+}
+
+// System API.
+
+// Log a message to the host console.
+pub fn system_log(message []u8) {
+	C.wasm96_system_log(&message[0], usize(message.len))
+}
+
+// Get the number of milliseconds since the app started.
+pub fn system_millis() u64 {
+	return C.wasm96_system_millis()
+}
+
+// Microseconds between the previous frame and this one, from the frontend's frame timing.
+pub fn system_delta_micros() u64 {
+	return C.wasm96_system_delta_micros()
+}
+
+// Number of frames the core has run since the game started.
+pub fn system_frame_count() u64 {
+	return C.wasm96_system_frame_count()
+}
+
+// Get the frontend's language and region as a BCP-47 style tag, e.g. 'en-US' or 'pt-BR'.
+pub fn system_get_locale() string {
+	mut buf := []u8{len: 64}
+	mut n := int(C.wasm96_system_get_locale(&buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_system_get_locale(&buf[0], usize(buf.len)))
+	}
+	return buf[..n].bytestr()
+}
+
+// Get a random 64-bit value from the host.
+// The host can seed this deterministically for netplay and replays.
+pub fn system_random_u64() u64 {
+	return C.wasm96_system_random_u64()
+}
+
+// Get the local date and time.
+// The host may override this with a fixed value for deterministic runs.
+pub fn system_datetime() DateTime {
+	packed := C.wasm96_system_datetime()
+	return DateTime{
+		year: u16(packed >> 48)
+		month: u8(packed >> 40)
+		day: u8(packed >> 32)
+		hour: u8(packed >> 24)
+		minute: u8(packed >> 16)
+		second: u8(packed >> 8)
+		weekday: u8(packed)
+	}
+}
+
+// Take the host's last error message, clearing it.
+// Returns an empty string if no call has failed since the last check.
+pub fn system_last_error() string {
+	mut buf := []u8{len: 256}
+	mut n := int(C.wasm96_system_last_error(&buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_system_last_error(&buf[0], usize(buf.len)))
+	}
+	return buf[..n].bytestr()
+}
+
+// Return a Wasm96Error if any host call has failed since the last check.
+// Use after drawing calls, which do not report failures directly.
+pub fn check_error() ! {
+	message := system_last_error()
+	if message.len > 0 {
+		return Wasm96Error{
+			message: message
+		}
+	}
+}
+
+fn host_error(fallback string) IError {
+	message := system_last_error()
+	return Wasm96Error{
+		message: if message.len > 0 { message } else { fallback }
+	}
+}
+
+// Get information about the host, such as the core version, ABI version,
+// runtime backend (wasmtime or wasmer) or frontend name.
+pub fn system_info(kind InfoKind) string {
+	mut buf := []u8{len: 64}
+	mut n := int(C.wasm96_system_info(u32(kind), &buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_system_info(u32(kind), &buf[0], usize(buf.len)))
+	}
+	return buf[..n].bytestr()
+}
+
+// Get the directory containing the loaded ROM, as reported by the frontend.
+// Returns an empty string if the frontend does not provide one.
+pub fn system_content_dir() string {
+	mut buf := []u8{len: 256}
+	mut n := int(C.wasm96_system_content_dir(&buf[0], usize(buf.len)))
+	if n > buf.len {
+		buf = []u8{len: n}
+		n = int(C.wasm96_system_content_dir(&buf[0], usize(buf.len)))
+	}
+	return buf[..n].bytestr()
+}
+
+// Get the display's target refresh rate in Hz, e.g. 60, 50 or 120.
+pub fn system_display_hz() f32 {
+	return C.wasm96_system_display_hz()
+}
+
+// Snapshot the current frame into a PNG resource registered under key, which can then
+// be drawn with graphics_png_draw_key. Returns false on failure.
+pub fn system_screenshot(key []u8) bool {
+	return C.wasm96_system_screenshot(&key[0], usize(key.len)) != 0
+}
+
+// Ask the frontend to close the game, e.g. from a Quit menu item.
+pub fn system_exit() {
+	C.wasm96_system_exit()
+}
+
+// Save the current frame as a timestamped PNG in the frontend's screenshot directory.
+pub fn debug_screenshot() {
+	C.wasm96_debug_screenshot()
+}
+
+// Start timing a named scope. Per-frame totals are shown in the host's debug overlay.
+pub fn perf_begin(name []u8) {
+	C.wasm96_perf_begin(&name[0], usize(name.len))
+}
+
+// Stop timing a named scope started with perf_begin.
+pub fn perf_end(name []u8) {
+	C.wasm96_perf_end(&name[0], usize(name.len))
+}
+
+// Log a fatal error with its source location to the host console, then halt.
+// Call as: wasm96.system_panic('bad state', @FILE, @LINE)
+@[noreturn]
+pub fn system_panic(message string, file string, line string) {
+	log(.error, 'panic at ${file}:${line}: ${message}')
+	panic(message)
+}
+
+// Log a message through the frontend's log interface with a severity level.
+// Messages below the minimum level set in the core options are dropped.
+// Use string interpolation for formatting: wasm96.log(.info, 'score = ${score}')
+pub fn log(level LogLevel, message string) {
+	if message.len == 0 {
+		return
+	}
+	C.wasm96_system_log_level(u32(level), message.str, usize(message.len))
+}
+
+// Log a debug message.
+pub fn log_debug(message string) {
+	log(.debug, message)
+}
+
+// Log an informational message.
+pub fn log_info(message string) {
+	log(.info, message)
+}
+
+// Log a warning.
+pub fn log_warn(message string) {
+	log(.warn, message)
+}
+
+// Log an error.
+pub fn log_error(message string) {
+	log(.error, message)
+}
+
+// Time API.
+
+// Frame timing helper built on the host's frame timing imports.
+// Call tick once per frame before reading any other value.
+pub struct Clock {
+mut:
+	delta       f32
+	frames      u64
+	accumulator f32
+pub mut:
+	// Longest delta reported, so a pause does not cause a huge catch-up.
+	max_delta f32 = 0.25
+}
+
+// Advance the clock by one frame.
+pub fn (mut c Clock) tick() {
+	c.delta = f32(system_delta_micros()) / 1000000
+	if c.delta > c.max_delta {
+		c.delta = c.max_delta
+	}
+	c.frames = system_frame_count()
+	c.accumulator += c.delta
+}
+
+// Seconds elapsed between the last two ticks.
+pub fn (c &Clock) delta_seconds() f32 {
+	return c.delta
+}
+
+// Number of frames the core has run.
+pub fn (c &Clock) frame_count() u64 {
+	return c.frames
+}
+
+// Consume one fixed timestep of dt seconds if enough time has accumulated.
+// Use as: for clock.step(1.0 / 60) { update() }
+pub fn (mut c Clock) step(dt f32) bool {
+	if c.accumulator < dt {
+		return false
+	}
+	c.accumulator -= dt
+	return true
+}
+
+// Fraction of a fixed timestep of dt seconds left in the accumulator, for interpolating draws.
+pub fn (c &Clock) alpha(dt f32) f32 {
+	return c.accumulator / dt
+}
+
+// Random API.
+
+// Seedable PCG32 random number generator.
+// The same seed always produces the same sequence, which keeps replays deterministic.
+pub struct Rng {
+mut:
+	state u64
+	inc   u64 = 1442695040888963407
+}
+
+// Create a generator from a seed.
+pub fn rng_new(seed u64) Rng {
+	mut r := Rng{}
+	r.next_u32()
+	r.state += seed
+	r.next_u32()
+	return r
+}
+
+// Create a generator seeded from the host clock.
+pub fn rng_from_time() Rng {
+	return rng_new(system_millis())
+}
+
+// Create a generator seeded from the host random source.
+// Prefer this over rng_from_time so seeds stay deterministic under netplay and replays.
+pub fn rng_from_host() Rng {
+	return rng_new(system_random_u64())
+}
+
+// Next random 32-bit value.
+pub fn (mut r Rng) next_u32() u32 {
+	old := r.state
+	r.state = old * 6364136223846793005 + r.inc
+	xorshifted := u32(((old >> 18) ^ old) >> 27)
+	rot := u32(old >> 59)
+	return (xorshifted >> rot) | (xorshifted << ((32 - rot) & 31))
+}
+
+// Random float in 0.0..1.0 (exclusive).
+pub fn (mut r Rng) next_f32() f32 {
+	return f32(r.next_u32() >> 8) / 16777216.0
+}
+
+// Random integer in min..max (max exclusive). Returns min if the range is empty.
+pub fn (mut r Rng) range(min int, max int) int {
+	if max <= min {
+		return min
+	}
+	span := u64(u32(max - min))
+	return min + int((u64(r.next_u32()) * span) >> 32)
+}
+
+// Random float in min..max.
+pub fn (mut r Rng) range_f32(min f32, max f32) f32 {
+	return min + (max - min) * r.next_f32()
+}
+
+// Returns true with probability p (0.0 to 1.0).
+pub fn (mut r Rng) chance(p f32) bool {
+	return r.next_f32() < p
+}
+
+// Pick a random element. items must not be empty.
+pub fn (mut r Rng) pick[T](items []T) T {
+	return items[r.range(0, items.len)]
+}
+
+// Shuffle items in place (Fisher-Yates).
+pub fn (mut r Rng) shuffle[T](mut items []T) {
+	for i := items.len - 1; i > 0; i-- {
+		j := r.range(0, i + 1)
+		items[i], items[j] = items[j], items[i]
+	}
 }